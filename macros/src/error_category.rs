@@ -5,12 +5,14 @@ use quote::quote;
 use std::ops::Deref;
 use syn::{
     parse::ParseStream, parse_quote, punctuated::Punctuated, token::Comma, Attribute, DeriveInput,
-    Expr, ExprLit, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path,
+    Expr, ExprLit, Fields, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type,
 };
 
 mod consts {
     /// The maximum value an error code can have.
     pub const MAX_ERROR_CODE: usize = 15;
+    /// The maximum value an error code of a wide category can have.
+    pub const MAX_WIDE_ERROR_CODE: usize = 255;
     /// Maximum number of links.
     pub const MAX_LINKS: usize = 6;
 
@@ -18,6 +20,12 @@ mod consts {
     pub const FMT_PLACEHOLDER_DETAILS: &str = "details";
     pub const FMT_PLACEHOLDER_VARIANT: &str = "variant";
     pub const FMT_PLACEHOLDER_CATEGORY: &str = "category";
+    /// The named format argument that prints the payload of an error.
+    pub const FMT_ARG_VALUE: &str = "value";
+    /// The `#[error(unknown)]` argument.
+    pub const UNKNOWN_ARG: &str = "unknown";
+    /// The `#[error(map(...))]` argument.
+    pub const MAP_ARG: &str = "map";
     pub const FMT_PLACEHOLDER_DELIM_L: char = '{';
     pub const FMT_PLACEHOLDER_DELIM_R: char = '}';
 }
@@ -26,56 +34,24 @@ mod consts {
 struct ErrorCategoryAttr {
     name: Option<String>,
     links: Vec<Path>,
-    /// This value is `true`, if an enum variant can be trivially converted to and from an
-    /// `ErrorCode`.
-    ///
-    /// For converting an error code back to a variant
-    /// `core::mem::transmute()` will be used. This is only valid if the size of the enum
-    /// type is equal to the size of `ErrorCode` (which is a `u8`). So for that to be
-    /// possible the enum must be `repr(u8)`. When the enum has no variants, `unreachable!()`
-    /// will be generated, because the type can never instantiated, so in that case it
-    /// being `repr(u8)` is optional.
-    is_repr_u8_compatible: bool,
+    /// `true` if the category has wide error codes (`#[error_category(wide)]`).
+    wide: bool,
+    /// The path of the foreign enum that is mirrored (`#[error_category(remote = "...")]`).
+    remote: Option<Path>,
 }
 
 impl ErrorCategoryAttr {
     /// Parse the `#[error_category(...)] attribute.
-    fn parse(input: &DeriveInput, has_variants: bool) -> ErrorCategoryAttr {
+    fn parse(input: &DeriveInput) -> ErrorCategoryAttr {
         // Get attribute `error_category`.
         // Error if multiple `error_category` attributes exist.
-        // Try to find `repr(u8)` attribute, if `has_variants` is `true`.
-        let (attr, is_repr_u8_compatible) = {
+        let attr = {
             let metas: Vec<_> = input
                 .attrs
                 .iter()
                 .filter_map(|a| a.parse_meta().map_err(|err| emit_error!(err)).ok())
                 .collect();
 
-            let is_repr_u8_compatible = !has_variants || {
-                let contains_repr_u8 = metas
-                    .iter()
-                    .filter_map(|m| {
-                        if let Meta::List(ml) = m {
-                            Some(ml)
-                        } else {
-                            None
-                        }
-                    })
-                    .find(|m| m.path.is_ident("repr"))
-                    .and_then(|ml| {
-                        ml.nested.iter().find(|nm| {
-                            if let NestedMeta::Meta(m) = *nm {
-                                if m.path().is_ident("u8") {
-                                    return true;
-                                }
-                            }
-                            false
-                        })
-                    })
-                    .is_some();
-                contains_repr_u8
-            };
-
             // get all `error_category` attributes
             let attrs: Vec<_> = metas
                 .iter()
@@ -93,27 +69,31 @@ impl ErrorCategoryAttr {
                 );
             }
 
-            (
-                attrs.first().map(Deref::deref).cloned(),
-                is_repr_u8_compatible,
-            )
+            attrs.first().map(Deref::deref).cloned()
         };
 
         if let Some(attr) = attr {
-            let (name_arg, links_arg, errors) = Self::validate_attr_args(attr.nested);
+            let (name_arg, remote_arg, links_arg, wide, errors) =
+                Self::validate_attr_args(attr.nested);
 
             // emit all the errors we got back
             errors.into_iter().for_each(|err| match err {
                 ErrorCategoryArgError::InvalidArg(m) => emit_error!(
                     m,
-                    "invalid attribute argument, expected `name = \"...\"` or `links(...)`"
+                    "invalid attribute argument, expected `name = \"...\"`, `remote = \"...\"`, `links(...)` or `wide`"
                 ),
                 ErrorCategoryArgError::TooManyNameArgs(m) => {
                     emit_error!(m, "at most one `name = \"...\" is allowed")
                 }
+                ErrorCategoryArgError::TooManyRemoteArgs(m) => {
+                    emit_error!(m, "at most one `remote = \"...\" is allowed")
+                }
                 ErrorCategoryArgError::TooManyLinksArgs(m) => {
                     emit_error!(m, "at most one `links(...)` is allowed")
                 }
+                ErrorCategoryArgError::TooManyWideArgs(m) => {
+                    emit_error!(m, "at most one `wide` is allowed")
+                }
             });
 
             // get the potential `name = "..."` literal
@@ -123,6 +103,16 @@ impl ErrorCategoryAttr {
                 _ => unreachable!(),
             });
 
+            // get and parse the potential `remote = "..."` path
+            let remote = remote_arg.and_then(|nv| match nv.lit {
+                // Note: This is already validated in `validate_error_category_attr_args()`
+                syn::Lit::Str(lit) => lit
+                    .parse::<Path>()
+                    .map_err(|err| emit_error!(lit, "expected path to an enum: {}", err))
+                    .ok(),
+                _ => unreachable!(),
+            });
+
             // validate and get the paths inside `links(...)`
             let links = links_arg
                 .map(|ml| {
@@ -155,32 +145,45 @@ impl ErrorCategoryAttr {
             ErrorCategoryAttr {
                 name,
                 links,
-                is_repr_u8_compatible,
+                wide,
+                remote,
             }
         } else {
-            ErrorCategoryAttr {
-                is_repr_u8_compatible,
-                ..ErrorCategoryAttr::default()
-            }
+            ErrorCategoryAttr::default()
         }
     }
 
     /// Validate `error_category` attribute args
     /// Parse `error_category` arguments:
     /// - one optional `name = "literal"`
+    /// - one optional `remote = "path"`
     /// - one optional `links(<type-list>)` where <type-list> is a comma seperated list of
     ///   0 to 4 types.
+    /// - one optional `wide`
     fn validate_attr_args(
         nested: Punctuated<NestedMeta, Comma>,
     ) -> (
+        Option<MetaNameValue>,
         Option<MetaNameValue>,
         Option<MetaList>,
+        bool,
         Vec<ErrorCategoryArgError>,
     ) {
-        let (args_matches, args_invalid): (Vec<_>, Vec<_>) =
-            nested.into_iter().partition(|nm| matches!(nm, NestedMeta::Meta(Meta::NameValue(_)) | NestedMeta::Meta(Meta::List(_))));
+        let (wide_args, nested): (Vec<_>, Vec<_>) = nested.into_iter().partition(
+            |nm| matches!(nm, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wide")),
+        );
+
+        let (args_matches, args_invalid): (Vec<_>, Vec<_>) = nested.into_iter().partition(|nm| {
+            matches!(
+                nm,
+                NestedMeta::Meta(Meta::NameValue(_)) | NestedMeta::Meta(Meta::List(_))
+            )
+        });
 
         let mut errors = Vec::new();
+        if wide_args.len() > 1 {
+            errors.push(ErrorCategoryArgError::TooManyWideArgs(wide_args[1].clone()));
+        }
         if !args_invalid.is_empty() {
             errors.push(ErrorCategoryArgError::InvalidArg(args_invalid[0].clone()));
         }
@@ -192,22 +195,33 @@ impl ErrorCategoryAttr {
                 _ => unreachable!(),
             });
 
-        // validate `name = "..."` args
-        let (name_args, invalid): (Vec<_>, Vec<_>) = name_value_args
+        // validate `name = "..."` and `remote = "..."` args
+        let (name_value_args, invalid): (Vec<_>, Vec<_>) = name_value_args
             .into_iter()
             .map(|nm| match nm {
                 NestedMeta::Meta(Meta::NameValue(nv)) => nv,
                 _ => unreachable!(),
             })
-            .partition(|nv| nv.path.is_ident("name") && matches!(nv.lit, syn::Lit::Str(_)));
+            .partition(|nv| {
+                (nv.path.is_ident("name") || nv.path.is_ident("remote"))
+                    && matches!(nv.lit, syn::Lit::Str(_))
+            });
         if !invalid.is_empty() {
             errors.push(ErrorCategoryArgError::InvalidArg(NestedMeta::Meta(
                 invalid[0].clone().into(),
             )));
         }
+        let (name_args, remote_args): (Vec<_>, Vec<_>) = name_value_args
+            .into_iter()
+            .partition(|nv| nv.path.is_ident("name"));
         if name_args.len() > 1 {
             errors.push(ErrorCategoryArgError::TooManyNameArgs(name_args[1].clone()));
         }
+        if remote_args.len() > 1 {
+            errors.push(ErrorCategoryArgError::TooManyRemoteArgs(
+                remote_args[1].clone(),
+            ));
+        }
 
         // validate `links(...)` args
         // Note: does not validate args inside `(...)`
@@ -231,9 +245,16 @@ impl ErrorCategoryAttr {
         }
 
         let name_arg = name_args.into_iter().next();
+        let remote_arg = remote_args.into_iter().next();
         let links_arg = links_args.into_iter().next();
 
-        (name_arg, links_arg, errors)
+        (
+            name_arg,
+            remote_arg,
+            links_arg,
+            !wide_args.is_empty(),
+            errors,
+        )
     }
 }
 
@@ -294,7 +315,25 @@ impl ErrorVariantAttr {
 
 struct ErrorVariant {
     variant_name: Ident,
+    /// The type of the single field of a tuple variant.
+    field_ty: Option<Type>,
+    /// The index of the `Infallible` field and the number of fields of a marker variant.
+    ///
+    /// Marker variants can never be instantiated, they only exist to use the generic
+    /// parameters of the enum (for example with a `PhantomData<B>` field).
+    marker: Option<(usize, usize)>,
+    /// `true` if the variant has an explicit discriminant.
+    has_discriminant: bool,
+    /// `true` if the variant is marked with `#[error(unknown)]`.
+    is_unknown: bool,
+    /// The variants of other categories that are converted into this variant
+    /// (`#[error(map(...))]`).
+    maps: Vec<Path>,
     format_str: Option<String>,
+    /// `true` if `format_str` uses the `{value}` format argument.
+    uses_value: bool,
+    /// `true` if `format_str` uses positional arguments, the first of which is the field.
+    uses_field: bool,
     doc_summary: String,
     doc_details: String,
     error_attr: Option<ErrorVariantAttr>,
@@ -386,8 +425,10 @@ impl ErrorVariant {
 
     /// Parse a enum variant.
     ///
-    /// Every enum variant can have one `#[error(...)]` attribute.
+    /// Every enum variant can have one `#[error(...)]` attribute, and any number of
+    /// `#[error(map(...))]` attributes.
     fn parse(variant: &syn::Variant) -> ErrorVariant {
+        let mut maps = Vec::new();
         // Get the `error` attribute.
         // Error there are multiple `error` attributes.
         let attr = {
@@ -395,17 +436,29 @@ impl ErrorVariant {
                 .attrs
                 .iter()
                 .filter(|a| a.path.is_ident("error"))
+                .filter(|a| match parse_map_attr(a) {
+                    Some(paths) => {
+                        maps.extend(paths);
+                        false
+                    }
+                    None => true,
+                })
                 .collect();
 
             if attrs.len() > 1 {
                 emit_error!(attrs[1], "too many `error` attributes"; note = "at most one `#[error(...)]` attribute is allowed");
             }
 
-            attrs.first().and_then(|a| {
-                a.parse_args_with(|ps: ParseStream<'_>| Ok(ErrorVariantAttr::parse(ps, a)))
-                    .unwrap()
-            })
+            attrs.first().copied()
         };
+        // `#[error(unknown)]` marks the variant that unknown error codes are converted to.
+        let is_unknown = attr
+            .and_then(|a| a.parse_args::<Ident>().ok())
+            .map_or(false, |ident| ident == consts::UNKNOWN_ARG);
+        let attr = attr.filter(|_| !is_unknown).and_then(|a| {
+            a.parse_args_with(|ps: ParseStream<'_>| Ok(ErrorVariantAttr::parse(ps, a)))
+                .unwrap()
+        });
         let parse_doc_comments = attr
             .as_ref()
             .map(|a| a.doc_comment_placeholder)
@@ -440,7 +493,7 @@ impl ErrorVariant {
                     .take_while(|(is_summary, _)| *is_summary)
                     .map(|(_, line)| line.trim().to_owned())
                     .collect::<Vec<String>>()
-                    .join(&" ");
+                    .join(" ");
                 let is_only_whitespace = summary.trim().is_empty();
                 if is_only_whitespace {
                     String::new()
@@ -458,7 +511,7 @@ impl ErrorVariant {
                 if is_only_whitespace {
                     String::new()
                 } else {
-                    details_lines.join(&"\n")
+                    details_lines.join("\n")
                 }
             };
 
@@ -467,17 +520,53 @@ impl ErrorVariant {
             (String::new(), String::new())
         };
 
-        if !variant.fields.is_empty() {
+        let marker = match &variant.fields {
+            Fields::Unnamed(fields) => fields
+                .unnamed
+                .iter()
+                .position(|field| is_infallible(&field.ty))
+                .map(|i| (i, fields.unnamed.len())),
+            _ => None,
+        };
+        let field_ty = match &variant.fields {
+            _ if marker.is_some() => None,
+            Fields::Unit => None,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Some(fields.unnamed[0].ty.clone())
+            }
+            fields => {
+                emit_error!(
+                    fields,
+                    "only unit variants and tuple variants with a single field are allowed when deriving `ErrorCategory`";
+                    help = "a marker variant that only uses the generic parameters of the enum must have a `core::convert::Infallible` field"
+                );
+                None
+            }
+        };
+        if is_unknown && (field_ty.is_some() || marker.is_some()) {
+            emit_error!(
+                variant.fields,
+                "the `#[error(unknown)]` variant must be a unit variant"
+            );
+        }
+        if !maps.is_empty() && (field_ty.is_some() || marker.is_some()) {
             emit_error!(
                 variant.fields,
-                "no fields allowed when deriving `ErrorCategory`"
+                "only unit variants can be used with `#[error(map(...))]`"
             );
         }
 
         ErrorVariant {
             error_attr: attr,
-            // This is set in `derive_error_category()`.
+            field_ty,
+            marker,
+            has_discriminant: variant.discriminant.is_some(),
+            is_unknown,
+            maps,
+            // These are set in `derive_error_category()`.
             format_str: None,
+            uses_value: false,
+            uses_field: false,
             doc_summary: summary,
             doc_details: details,
             variant_name: variant.ident.clone(),
@@ -488,7 +577,9 @@ impl ErrorVariant {
 enum ErrorCategoryArgError {
     InvalidArg(NestedMeta),
     TooManyNameArgs(MetaNameValue),
+    TooManyRemoteArgs(MetaNameValue),
     TooManyLinksArgs(MetaList),
+    TooManyWideArgs(NestedMeta),
 }
 
 /// Derive the traits `ErrorCategory`, `From<ErrorCode>`, `Into<ErrorCode>` and `core::fmt::Debug`
@@ -503,13 +594,32 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
     .map(ErrorVariant::parse)
     .collect();
     // parse the optional `#[error_category(...)]` attribute
-    let error_category_attr = ErrorCategoryAttr::parse(&input, !variants.is_empty());
+    let error_category_attr = ErrorCategoryAttr::parse(&input);
 
-    let enum_ident = input.ident;
+    // Unknown error codes are converted to the `#[error(unknown)]` variant.
+    let unknown_variant = {
+        let mut unknown_variants = variants.iter().filter(|v| v.is_unknown);
+        let unknown_variant = unknown_variants.next().map(|v| v.variant_name.clone());
+        for v in unknown_variants {
+            emit_error!(
+                v.variant_name,
+                "at most one variant can be marked with `#[error(unknown)]`"
+            );
+        }
+        unknown_variant
+    };
+    let enum_ident = &input.ident;
     let name_str = error_category_attr
         .name
         .unwrap_or_else(|| enum_ident.to_string());
     let links = error_category_attr.links;
+    let wide = error_category_attr.wide;
+    let remote = error_category_attr.remote;
+    let max_error_code = if wide {
+        consts::MAX_WIDE_ERROR_CODE
+    } else {
+        consts::MAX_ERROR_CODE
+    };
 
     // replace placeholders in format string
     for v in variants.iter_mut() {
@@ -560,8 +670,78 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
             consts::FMT_PLACEHOLDER_DELIM_R,
         );
 
+        v.uses_value = str_placeholder::contains_named_arg(&format_str, consts::FMT_ARG_VALUE);
+        v.uses_field = v.field_ty.is_some() && str_placeholder::uses_positional_args(&format_str);
         v.format_str = Some(format_str);
     }
+    let uses_value = variants.iter().any(|v| v.uses_value);
+
+    // All fields of tuple variants must have the same type, which is the payload type of
+    // the category.
+    let payload_ty = {
+        let mut field_tys = variants.iter().filter_map(|v| v.field_ty.as_ref());
+        let payload_ty = field_tys.next().cloned();
+        if let Some(payload_ty) = &payload_ty {
+            let payload_ty_str = quote!(#payload_ty).to_string();
+            for ty in field_tys.filter(|ty| quote!(#ty).to_string() != payload_ty_str) {
+                emit_error!(ty, "all variant fields must have the same type"; note = "the first field has type `{}`", quote!(#payload_ty));
+            }
+        }
+        payload_ty
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let type_params: Vec<_> = input.generics.type_params().map(|p| &p.ident).collect();
+
+    let fmt_with_value_impl = if uses_value || payload_ty.is_some() {
+        let match_arms: Vec<_> = variants
+            .iter()
+            .map(|v| {
+                marker_arm(v, quote!(Self)).unwrap_or_else(|| {
+                    let pattern = variant_pattern(v, quote!(_));
+                    // The payload is formatted in place of the field.
+                    let write = write_variant(
+                        v,
+                        v.uses_field.then(|| quote! { value }),
+                        v.uses_value.then(|| quote! { value }),
+                    );
+
+                    if v.uses_field {
+                        // Without a payload the field is unknown, so it is not printed.
+                        let fallback = if v.doc_summary.is_empty() {
+                            v.variant_name.to_string()
+                        } else {
+                            v.doc_summary.clone()
+                        };
+                        quote! {
+                            #pattern => match value {
+                                ::embedded_error_chain::PayloadValue::Missing => f.write_str(#fallback),
+                                value => #write,
+                            }
+                        }
+                    } else {
+                        quote! {
+                            #pattern => #write
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        quote! {
+            fn fmt_with_value(
+                &self,
+                value: ::embedded_error_chain::PayloadValue,
+                f: &mut ::embedded_error_chain::utils::fmt::Formatter<'_>,
+            ) -> ::embedded_error_chain::utils::fmt::Result {
+                match *self {
+                    #(#match_arms),*
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
     let error_category_impl = {
         let assoc_types: Vec<_> = links
@@ -579,71 +759,250 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
             })
             .collect();
 
+        let wide_const = if wide {
+            quote! { const WIDE: bool = true; }
+        } else {
+            quote!()
+        };
+        // Links that depend on a generic parameter can't be checked at the item level,
+        // chaining a wide error code still panics in debug builds (see
+        // `ErrorData::push_front()`).
+        let link_checks: Vec<_> = links
+            .iter()
+            .filter(|link| {
+                !link
+                    .segments
+                    .first()
+                    .map_or(false, |segment| type_params.contains(&&segment.ident))
+            })
+            .map(|link| {
+                let msg = format!(
+                    "`{}` is a wide error category and cannot be linked by `{}`",
+                    quote!(#link).to_string().replace(' ', ""),
+                    enum_ident
+                );
+                quote! {
+                    ::embedded_error_chain::const_assert!(!<#link as ::embedded_error_chain::ErrorCategory>::WIDE, #msg);
+                }
+            })
+            .collect();
+
         quote! {
-            impl ::embedded_error_chain::ErrorCategory for #enum_ident {
+            #(#link_checks)*
+
+            impl #impl_generics ::embedded_error_chain::ErrorCategory for #enum_ident #ty_generics #where_clause {
                 const NAME: &'static str = #name_str;
+                #wide_const
 
                 #(#assoc_types)*
 
                 fn chainable_category_formatters() -> &'static [::embedded_error_chain::ErrorCodeFormatter] {
                     &[#( ::embedded_error_chain::format_chained::<#links> ),*]
                 }
+
+                #fmt_with_value_impl
             }
         }
     };
 
-    let from_into_impls = if error_category_attr.is_repr_u8_compatible {
-        let discriminant_value_checks: Vec<_> = variants.iter().map(|variant| {
-            let max_val_plus_one = (consts::MAX_ERROR_CODE as isize) + 1;
-            let variant_name = variant.variant_name.clone();
+    // Marker variants don't have an error code.
+    let code_variants: Vec<_> = variants.iter().filter(|v| v.marker.is_none()).collect();
+    let has_fields = code_variants.len() != variants.len() || payload_ty.is_some();
+
+    let from_into_impls = if has_fields {
+        // Variants with fields can't be cast to an integer, so the error code of every
+        // variant is its index and the conversions are implemented using `match`.
+        if code_variants.len() > max_error_code + 1 {
+            emit_error!(
+                enum_ident,
+                "too many variants, at most {} variants are allowed",
+                max_error_code + 1;
+                help = "use `#[error_category(wide)]` to allow up to {} variants", consts::MAX_WIDE_ERROR_CODE + 1
+            );
+        }
+        for v in variants.iter().filter(|v| v.has_discriminant) {
+            emit_error!(
+                v.variant_name,
+                "explicit discriminants are not allowed if a variant has a field";
+                note = "the error code of every variant is its index"
+            );
+        }
+
+        let codes: Vec<_> = (0..code_variants.len())
+            .map(|i| i as ::std::primitive::u8)
+            .collect();
+        let default_values: Vec<_> = code_variants
+            .iter()
+            .map(|v| {
+                let variant_name = &v.variant_name;
+                if v.field_ty.is_some() {
+                    quote! { Self::#variant_name(::core::default::Default::default()) }
+                } else {
+                    quote! { Self::#variant_name }
+                }
+            })
+            .collect();
+        let patterns: Vec<_> = code_variants
+            .iter()
+            .map(|v| {
+                let variant_name = &v.variant_name;
+                if v.field_ty.is_some() {
+                    quote! { #enum_ident::#variant_name(..) }
+                } else {
+                    quote! { #enum_ident::#variant_name }
+                }
+            })
+            .collect();
+        let marker_arms: Vec<_> = variants
+            .iter()
+            .filter_map(|v| marker_arm(v, quote!(#enum_ident)))
+            .collect();
+        let fallback_value = match (&unknown_variant, default_values.last()) {
+            (Some(unknown_variant), _) => quote! { Self::#unknown_variant },
+            (None, Some(last_default_value)) => quote! {
+                ::embedded_error_chain::utils::invalid_error_code();
+                #last_default_value
+            },
+            (None, None) => quote! { ::embedded_error_chain::uninhabited_error_code!(#enum_ident) },
+        };
+
+        let payload_category_impl = if let Some(payload_ty) = &payload_ty {
+            let (field_patterns, field_codes): (Vec<_>, Vec<_>) = code_variants
+                .iter()
+                .zip(&codes)
+                .filter(|(v, _)| v.field_ty.is_some())
+                .map(|(v, code)| {
+                    let variant_name = &v.variant_name;
+                    (quote! { Self::#variant_name }, *code)
+                })
+                .unzip();
 
-            let non_negative_msg = format!("`{}::{}` variant discriminant must not be negative", enum_ident.to_string(), variant_name.to_string());
-            let err_msg = format!("`{}::{}` variant discriminant must be less than {}", enum_ident.to_string(), variant_name.to_string(), max_val_plus_one);
             quote! {
-                ::embedded_error_chain::const_assert!((#enum_ident::#variant_name as isize) >= 0, #non_negative_msg);
-                ::embedded_error_chain::const_assert!((#enum_ident::#variant_name as isize) < #max_val_plus_one, #err_msg);
+                #[automatically_derived]
+                impl #impl_generics ::embedded_error_chain::PayloadCategory for #enum_ident #ty_generics #where_clause {
+                    type Payload = #payload_ty;
+
+                    fn payload(&self) -> #payload_ty {
+                        match *self {
+                            #(#field_patterns(payload) => payload,)*
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::default::Default::default(),
+                        }
+                    }
+
+                    fn from_payload(error_code: ::embedded_error_chain::ErrorCode, payload: #payload_ty) -> Self {
+                        match error_code {
+                            #(#field_codes => #field_patterns(payload),)*
+                            _ => <Self as ::embedded_error_chain::utils::From<::embedded_error_chain::ErrorCode>>::from(error_code),
+                        }
+                    }
+                }
             }
-        }).collect();
+        } else {
+            quote!()
+        };
 
-        let from_error_code_impl = {
-            let variant_vals = {
-                let vals: Vec<_> = variants
-                    .iter()
-                    .map(|v| {
-                        let variant_name = v.variant_name.clone();
-                        quote! {
-                            (#enum_ident::#variant_name as ::embedded_error_chain::ErrorCode)
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::embedded_error_chain::utils::From<::embedded_error_chain::ErrorCode> for #enum_ident #ty_generics #where_clause {
+                fn from(val: ::embedded_error_chain::ErrorCode) -> Self {
+                    match val {
+                        #(#codes => #default_values,)*
+                        _ => {
+                            #fallback_value
                         }
-                    })
-                    .collect();
+                    }
+                }
+            }
 
-                if vals.is_empty() {
-                    vec![quote! { val }]
-                } else {
-                    vals
+            #[automatically_derived]
+            impl #impl_generics ::embedded_error_chain::utils::From<#enum_ident #ty_generics> for ::embedded_error_chain::ErrorCode #where_clause {
+                fn from(val: #enum_ident #ty_generics) -> ::embedded_error_chain::ErrorCode {
+                    match val {
+                        #(#patterns => #codes,)*
+                        #(#marker_arms,)*
+                    }
                 }
+            }
+
+            #payload_category_impl
+        }
+    } else {
+        // The discriminants of a generic enum can't be checked at the item level, so they
+        // are checked by an associated const that is evaluated by the conversions.
+        let is_generic = !input.generics.params.is_empty();
+        let discriminant_value_checks: Vec<_> = variants.iter().map(|variant| {
+            let max_val_plus_one = (max_error_code as isize) + 1;
+            let variant_name = variant.variant_name.clone();
+
+            let non_negative_msg = format!("`{}::{}` variant discriminant must not be negative", enum_ident, variant_name);
+            let err_msg = if wide {
+                format!("`{}::{}` variant discriminant must be less than {}", enum_ident, variant_name, max_val_plus_one)
+            } else {
+                format!("`{}::{}` variant discriminant must be less than {} (use `#[error_category(wide)]` for larger discriminants)", enum_ident, variant_name, max_val_plus_one)
             };
+            if is_generic {
+                quote! {
+                    ::core::assert!((Self::#variant_name as isize) >= 0, #non_negative_msg);
+                    ::core::assert!((Self::#variant_name as isize) < #max_val_plus_one, #err_msg);
+                }
+            } else {
+                quote! {
+                    ::embedded_error_chain::const_assert!((#enum_ident::#variant_name as isize) >= 0, #non_negative_msg);
+                    ::embedded_error_chain::const_assert!((#enum_ident::#variant_name as isize) < #max_val_plus_one, #err_msg);
+                }
+            }
+        }).collect();
+        let (discriminant_value_checks, check_discriminants) = if is_generic && !variants.is_empty()
+        {
+            (
+                vec![quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #enum_ident #ty_generics #where_clause {
+                        const __CHECK_DISCRIMINANTS: () = {
+                            #(#discriminant_value_checks)*
+                        };
+                    }
+                }],
+                quote! { let () = Self::__CHECK_DISCRIMINANTS; },
+            )
+        } else {
+            (discriminant_value_checks, quote!())
+        };
 
+        let from_error_code_impl = {
+            // The error code of every variant is its discriminant, which is converted back
+            // to the variant using a match table.
             let logic = if variants.is_empty() {
-                quote! { unreachable!() }
-            } else if variants.len() == 1 {
-                let variant_name = variants[0].variant_name.clone();
-
-                quote! { #enum_ident::#variant_name }
+                quote! { match val { _ => ::embedded_error_chain::uninhabited_error_code!(#enum_ident) } }
             } else {
+                let variant_names: Vec<_> = variants.iter().map(|v| &v.variant_name).collect();
+                let fallback_value = match &unknown_variant {
+                    Some(unknown_variant) => quote! { Self::#unknown_variant },
+                    None => {
+                        let last_variant_name = variant_names.last();
+                        quote! {
+                            ::embedded_error_chain::utils::invalid_error_code();
+                            Self::#last_variant_name
+                        }
+                    }
+                };
+
                 quote! {
-                    unsafe { ::embedded_error_chain::utils::mem::transmute::<u8, Self>(val as u8) }
+                    #check_discriminants
+                    match val {
+                        #(_ if val == Self::#variant_names as ::embedded_error_chain::ErrorCode => Self::#variant_names,)*
+                        _ => {
+                            #fallback_value
+                        }
+                    }
                 }
             };
 
             quote! {
                 #[automatically_derived]
-                impl ::embedded_error_chain::utils::From<::embedded_error_chain::ErrorCode> for #enum_ident {
-                    fn from(val: ::embedded_error_chain::ErrorCode) -> #enum_ident {
-                        debug_assert!(
-                            #(#variant_vals == val)||*,
-                            "tried to convert invalid error code to category type"
-                        );
+                impl #impl_generics ::embedded_error_chain::utils::From<::embedded_error_chain::ErrorCode> for #enum_ident #ty_generics #where_clause {
+                    fn from(val: ::embedded_error_chain::ErrorCode) -> Self {
                         #logic
                     }
                 }
@@ -654,12 +1013,15 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
             let logic = if variants.is_empty() {
                 quote! { match self {} }
             } else {
-                quote! { self as ::embedded_error_chain::ErrorCode }
+                quote! {
+                    #check_discriminants
+                    self as ::embedded_error_chain::ErrorCode
+                }
             };
 
             quote! {
                 #[automatically_derived]
-                impl ::embedded_error_chain::utils::Into<::embedded_error_chain::ErrorCode> for #enum_ident {
+                impl #impl_generics ::embedded_error_chain::utils::Into<::embedded_error_chain::ErrorCode> for #enum_ident #ty_generics #where_clause {
                     fn into(self) -> ::embedded_error_chain::ErrorCode {
                         #logic
                     }
@@ -672,37 +1034,193 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
             #from_error_code_impl
             #into_error_code_impl
         }
-    } else {
-        quote!()
     };
 
-    let fmt_debug_impl = {
-        let match_arms: Vec<_> = variants
-            .into_iter()
+    // A remote category mirrors the variants of a foreign enum by name, so that values of
+    // the foreign enum can be converted into the category.
+    let remote_impls = if let Some(remote) = &remote {
+        if !input.generics.params.is_empty() {
+            emit_error!(
+                input.generics,
+                "`remote = \"...\"` is not supported for generic enums"
+            );
+        }
+
+        let mirrored_variants: Vec<_> = code_variants.iter().filter(|v| !v.is_unknown).collect();
+        let (remote_patterns, patterns): (Vec<_>, Vec<_>) = mirrored_variants
+            .iter()
             .map(|v| {
-                let write = match (v.format_str, v.error_attr) {
-                    (Some(format_str), Some(ErrorVariantAttr { format_args, .. }))
-                        if !format_args.is_empty() =>
-                    {
-                        quote! { ::core::write!(f, #format_str, #(#format_args),*) }
+                let variant_name = &v.variant_name;
+                if v.field_ty.is_some() {
+                    (
+                        quote! { #remote::#variant_name(field) },
+                        quote! { #enum_ident::#variant_name(field) },
+                    )
+                } else {
+                    (
+                        quote! { #remote::#variant_name },
+                        quote! { #enum_ident::#variant_name },
+                    )
+                }
+            })
+            .unzip();
+
+        // A foreign `#[non_exhaustive]` enum can only be mirrored with an unknown variant,
+        // which in turn can't be converted back.
+        match &unknown_variant {
+            Some(unknown_variant) => quote! {
+                #[automatically_derived]
+                impl ::embedded_error_chain::utils::From<#remote> for #enum_ident {
+                    fn from(val: #remote) -> #enum_ident {
+                        match val {
+                            #(#remote_patterns => #patterns,)*
+                            #[allow(unreachable_patterns)]
+                            _ => #enum_ident::#unknown_variant,
+                        }
+                    }
+                }
+            },
+            None => {
+                let marker_arms: Vec<_> = variants
+                    .iter()
+                    .filter_map(|v| marker_arm(v, quote!(#enum_ident)))
+                    .collect();
+
+                quote! {
+                    #[automatically_derived]
+                    impl ::embedded_error_chain::utils::From<#remote> for #enum_ident {
+                        fn from(val: #remote) -> #enum_ident {
+                            match val {
+                                #(#remote_patterns => #patterns,)*
+                            }
+                        }
                     }
-                    (Some(format_str), _) => quote! { ::core::write!(f, #format_str) },
-                    (None, _) => {
-                        let variant_name = v.variant_name.to_string();
-                        quote! { ::core::write!(f, #variant_name) }
+
+                    #[automatically_derived]
+                    impl ::embedded_error_chain::utils::From<#enum_ident> for #remote {
+                        fn from(val: #enum_ident) -> #remote {
+                            match val {
+                                #(#patterns => #remote_patterns,)*
+                                #(#marker_arms,)*
+                            }
+                        }
                     }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // Every category that is mentioned in a `#[error(map(...))]` attribute is converted
+    // into this category by replacing the most recent error code of an error, so the
+    // links of that category must be compatible with the links of this category.
+    let from_category_impls = {
+        let mut categories: Vec<(Path, Vec<(&Path, &Ident)>)> = Vec::new();
+        for v in variants.iter() {
+            for source in v.maps.iter() {
+                let category = Path {
+                    leading_colon: source.leading_colon,
+                    segments: source
+                        .segments
+                        .iter()
+                        .take(source.segments.len() - 1)
+                        .cloned()
+                        .collect(),
                 };
-                let variant_name = &v.variant_name;
+                let category_str = quote!(#category).to_string();
+                match categories
+                    .iter_mut()
+                    .find(|(c, _)| quote!(#c).to_string() == category_str)
+                {
+                    Some((_, sources)) => sources.push((source, &v.variant_name)),
+                    None => categories.push((category, vec![(source, &v.variant_name)])),
+                }
+            }
+        }
+
+        if !categories.is_empty() && !input.generics.params.is_empty() {
+            emit_error!(
+                input.generics,
+                "`#[error(map(...))]` is not supported for generic enums"
+            );
+        }
+
+        let fallback_arm = unknown_variant.as_ref().map(|unknown_variant| {
+            quote! {
+                #[allow(unreachable_patterns)]
+                _ => #enum_ident::#unknown_variant,
+            }
+        });
+
+        categories
+            .iter()
+            .map(|(category, sources)| {
+                let link_checks = (0..consts::MAX_LINKS).map(|i| {
+                    let link_ident = Ident::new(&format!("L{}", i), Span::call_site());
+                    match links.get(i) {
+                        Some(link) => quote! {
+                            ::embedded_error_chain::utils::assert_link_compatible::<
+                                <#category as ::embedded_error_chain::ErrorCategory>::#link_ident,
+                                #link,
+                                _,
+                            >();
+                        },
+                        None => quote! {
+                            ::embedded_error_chain::utils::assert_link_unused::<
+                                <#category as ::embedded_error_chain::ErrorCategory>::#link_ident,
+                            >();
+                        },
+                    }
+                });
+                let (source_paths, target_variants): (Vec<_>, Vec<_>) =
+                    sources.iter().cloned().unzip();
 
                 quote! {
-                    Self::#variant_name => #write
+                    const _: fn() = || {
+                        #(#link_checks)*
+                    };
+
+                    #[automatically_derived]
+                    impl ::embedded_error_chain::FromCategory<#category> for #enum_ident {
+                        fn from_category(error_code: #category) -> #enum_ident {
+                            match error_code {
+                                #(#source_paths { .. } => #enum_ident::#target_variants,)*
+                                #fallback_arm
+                            }
+                        }
+                    }
                 }
             })
+            .collect::<TokenStream>()
+    };
+
+    let fmt_debug_impl = {
+        let match_arms: Vec<_> = variants
+            .iter()
+            .map(|v| {
+                marker_arm(v, quote!(Self)).unwrap_or_else(|| {
+                    // Without a payload, `{value}` is printed as `?`.
+                    let write = write_variant(
+                        v,
+                        v.uses_field.then(|| quote! { field }),
+                        v.uses_value
+                            .then(|| quote! { ::embedded_error_chain::PayloadValue::Missing }),
+                    );
+                    let pattern = variant_pattern(v, quote!(field));
+
+                    quote! {
+                        #pattern => #write
+                    }
+                })
+            })
             .collect();
 
+        // Note: This impl is intentionally not marked `#[automatically_derived]`, because
+        // the format arguments are user code and must count as a use of the items they
+        // reference (the compiler ignores derived `Debug` impls for dead code analysis).
         quote! {
-            #[automatically_derived]
-            impl ::embedded_error_chain::utils::Debug for #enum_ident {
+            impl #impl_generics ::embedded_error_chain::utils::Debug for #enum_ident #ty_generics #where_clause {
                 fn fmt(&self, f: &mut ::embedded_error_chain::utils::fmt::Formatter<'_>)
                 -> ::embedded_error_chain::utils::fmt::Result {
                     match *self {
@@ -716,6 +1234,108 @@ pub fn derive_error_category(input: DeriveInput) -> TokenStream {
     quote! {
         #error_category_impl
         #from_into_impls
+        #remote_impls
+        #from_category_impls
         #fmt_debug_impl
     }
 }
+
+/// Parse the paths in the `#[error(map(...))]` attribute `attr`, or return `None` if
+/// `attr` is not such an attribute.
+fn parse_map_attr(attr: &Attribute) -> Option<Vec<Path>> {
+    let nested = match attr.parse_meta().ok()? {
+        Meta::List(MetaList { nested, .. }) => nested,
+        _ => return None,
+    };
+    match nested.first() {
+        Some(NestedMeta::Meta(Meta::List(ml)))
+            if nested.len() == 1 && ml.path.is_ident(consts::MAP_ARG) =>
+        {
+            Some(
+                ml.nested
+                    .iter()
+                    .filter_map(|nm| match nm {
+                        NestedMeta::Meta(Meta::Path(path)) if path.segments.len() > 1 => {
+                            Some(path.clone())
+                        }
+                        nm => {
+                            emit_error!(
+                                nm,
+                                "expected path to a variant of another error category, like `SubError::Timeout`"
+                            );
+                            None
+                        }
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Check if `ty` is the `Infallible` type.
+fn is_infallible(ty: &Type) -> bool {
+    match ty {
+        Type::Path(ty) => ty
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Infallible"),
+        _ => false,
+    }
+}
+
+/// Generate the match arm for the marker `variant` of the enum `enum_path`.
+///
+/// Because a marker variant contains an `Infallible` value, the arm is unreachable.
+fn marker_arm(variant: &ErrorVariant, enum_path: TokenStream) -> Option<TokenStream> {
+    let (index, len) = variant.marker?;
+    let variant_name = &variant.variant_name;
+    let fields = (0..len).map(|i| {
+        if i == index {
+            quote! { never }
+        } else {
+            quote! { _ }
+        }
+    });
+
+    Some(quote! {
+        #enum_path::#variant_name(#(#fields),*) => match never {}
+    })
+}
+
+/// Generate the pattern that matches `variant`, binding its field to `field_pat`.
+fn variant_pattern(variant: &ErrorVariant, field_pat: TokenStream) -> TokenStream {
+    let variant_name = &variant.variant_name;
+    match (&variant.field_ty, variant.uses_field) {
+        (Some(_), true) => quote! { Self::#variant_name(#field_pat) },
+        (Some(_), false) => quote! { Self::#variant_name(_) },
+        (None, _) => quote! { Self::#variant_name },
+    }
+}
+
+/// Generate the `write!()` call that debug formats `variant`.
+///
+/// If `field` is `Some`, it is passed as the first positional format argument. If `value`
+/// is `Some`, it is passed as the named `value` format argument.
+fn write_variant(
+    variant: &ErrorVariant,
+    field: Option<TokenStream>,
+    value: Option<TokenStream>,
+) -> TokenStream {
+    let field_arg = field.map(|field| quote! { , #field });
+    let value_arg = value.map(|value| quote! { , value = #value });
+
+    match (&variant.format_str, &variant.error_attr) {
+        (Some(format_str), Some(ErrorVariantAttr { format_args, .. }))
+            if !format_args.is_empty() =>
+        {
+            quote! { ::core::write!(f, #format_str #field_arg, #(#format_args),* #value_arg) }
+        }
+        (Some(format_str), _) => quote! { ::core::write!(f, #format_str #field_arg #value_arg) },
+        (None, _) => {
+            let variant_name = variant.variant_name.to_string();
+            quote! { ::core::write!(f, #variant_name) }
+        }
+    }
+}
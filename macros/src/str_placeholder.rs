@@ -39,6 +39,48 @@ pub fn first_placeholder_range(
     StrPlaceholderRangeIter::new(placeholder, left_delim, right_delim).next_range(string)
 }
 
+/// Whether `string` contains a format argument named `name`, with or without a format
+/// spec (`{name}` or `{name:...}`).
+///
+/// An argument is recognized if it is preceded by an odd number of `{` characters.
+pub fn contains_named_arg(string: &str, name: &str) -> bool {
+    string.match_indices(name).any(|(start_index, _)| {
+        let is_left_delim = is_delimited(
+            string[..start_index]
+                .chars()
+                .rev()
+                .take_while(|c| *c == '{')
+                .count(),
+        );
+        let next_char = string[start_index + name.len()..].chars().next();
+
+        is_left_delim && matches!(next_char, Some('}') | Some(':'))
+    })
+}
+
+/// Whether the format string `string` contains at least one positional argument (`{}`,
+/// `{0}`, `{:?}` or `{0:?}` for example).
+///
+/// Escaped braces (`{{` and `}}`) are ignored.
+pub fn uses_positional_args(string: &str) -> bool {
+    let mut rest = string;
+    while let Some(start_index) = rest.find('{') {
+        rest = &rest[start_index + 1..];
+        if let Some(stripped) = rest.strip_prefix('{') {
+            rest = stripped;
+            continue;
+        }
+
+        let end_index = rest.find('}').unwrap_or(rest.len());
+        let arg = rest[..end_index].split(':').next().unwrap_or("").trim();
+        if arg.chars().all(|c| c.is_ascii_digit()) {
+            return true;
+        }
+        rest = &rest[end_index..];
+    }
+    false
+}
+
 struct StrPlaceholderRangeIter<'a> {
     placeholder: &'a str,
     left_delim: char,
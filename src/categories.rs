@@ -0,0 +1,241 @@
+//! Ready-made [error categories](crate::ErrorCategory) for common embedded errors.
+//!
+//! The bus categories ([`SpiError`], [`I2cError`], [`SerialError`] and [`DigitalError`])
+//! are aligned with the error kinds of
+//! [embedded-hal](https://crates.io/crates/embedded-hal), and there are categories for
+//! the errors of [`core::fmt`] and [`core::num`]. Independent driver crates can link
+//! against these shared categories instead of each defining their own.
+//!
+//! Errors of other crates are converted into these categories by implementing [`From`]
+//! (which the orphan rules allow in the crate that defines the error):
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+//! use embedded_error_chain::{categories::SpiError, prelude::*};
+//!
+//! // An error of a third-party HAL.
+//! enum HalSpiError {
+//!     Overrun,
+//!     ModeFault,
+//!     Dma,
+//! }
+//!
+//! impl From<HalSpiError> for SpiError {
+//!     fn from(err: HalSpiError) -> SpiError {
+//!         match err {
+//!             HalSpiError::Overrun => SpiError::Overrun,
+//!             HalSpiError::ModeFault => SpiError::ModeFault,
+//!             HalSpiError::Dma => SpiError::Other,
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(Clone, Copy, ErrorCategory)]
+//! #[error_category(links(SpiError))]
+//! #[repr(u8)]
+//! enum SensorError {
+//!     ReadoutFailed,
+//! }
+//!
+//! fn spi_read() -> Result<u8, HalSpiError> {
+//!     Err(HalSpiError::Overrun)
+//! }
+//!
+//! fn read_sensor() -> Result<u8, Error<SensorError>> {
+//!     spi_read()
+//!         .map_err(SpiError::from)
+//!         .chain_err(SensorError::ReadoutFailed)
+//! }
+//!
+//! assert!(read_sensor().unwrap_err().caused_by(SpiError::Overrun));
+//! ```
+//!
+//! All categories are `#[non_exhaustive]`, new variants are only added at the end so
+//! that the error codes of existing variants never change.
+
+use core::num::IntErrorKind;
+
+crate::error_category! {
+    /// Errors that occur in many different drivers.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum CommonError {
+        /// timeout
+        Timeout,
+        /// busy
+        Busy,
+        /// overrun
+        Overrun,
+        /// no acknowledge
+        Nack,
+        /// invalid argument
+        InvalidArgument,
+        /// not supported
+        NotSupported,
+        /// other error
+        Other,
+    }
+}
+
+crate::error_category! {
+    /// SPI errors, aligned with `embedded_hal::spi::ErrorKind`.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum SpiError {
+        /// overrun
+        ///
+        /// The peripheral receive buffer was overrun.
+        Overrun,
+        /// mode fault
+        ///
+        /// Multiple devices on the SPI bus are trying to drive the slave select pin.
+        ModeFault,
+        /// frame format error
+        FrameFormat,
+        /// chip select fault
+        ChipSelectFault,
+        /// other error
+        Other,
+    }
+}
+
+crate::error_category! {
+    /// I2C errors, aligned with `embedded_hal::i2c::ErrorKind`.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum I2cError {
+        /// bus error
+        Bus,
+        /// arbitration lost
+        ArbitrationLoss,
+        /// no acknowledge
+        ///
+        /// The source of the missing acknowledge is unknown.
+        NoAcknowledge,
+        /// no acknowledge of the address
+        NoAcknowledgeAddress,
+        /// no acknowledge of the data
+        NoAcknowledgeData,
+        /// overrun
+        Overrun,
+        /// other error
+        Other,
+    }
+}
+
+crate::error_category! {
+    /// Serial errors, aligned with `embedded_hal_nb::serial::ErrorKind`.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum SerialError {
+        /// overrun
+        Overrun,
+        /// frame format error
+        FrameFormat,
+        /// parity error
+        Parity,
+        /// noise error
+        Noise,
+        /// other error
+        Other,
+    }
+}
+
+crate::error_category! {
+    /// Digital I/O errors, aligned with `embedded_hal::digital::ErrorKind`.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum DigitalError {
+        /// other error
+        Other,
+    }
+}
+
+crate::error_category! {
+    /// The error of [`core::fmt`].
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum FmtError {
+        /// formatting failed
+        Error,
+    }
+}
+
+impl From<core::fmt::Error> for FmtError {
+    fn from(_: core::fmt::Error) -> FmtError {
+        FmtError::Error
+    }
+}
+
+crate::error_category! {
+    /// Errors of parsing an integer, aligned with [`core::num::IntErrorKind`].
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum ParseIntError {
+        /// cannot parse integer from empty string
+        Empty,
+        /// invalid digit found in string
+        InvalidDigit,
+        /// number too large to fit in target type
+        PosOverflow,
+        /// number too small to fit in target type
+        NegOverflow,
+        /// number would be zero for non-zero type
+        Zero,
+        /// other error
+        Other,
+    }
+}
+
+impl From<core::num::ParseIntError> for ParseIntError {
+    fn from(err: core::num::ParseIntError) -> ParseIntError {
+        match err.kind() {
+            IntErrorKind::Empty => ParseIntError::Empty,
+            IntErrorKind::InvalidDigit => ParseIntError::InvalidDigit,
+            IntErrorKind::PosOverflow => ParseIntError::PosOverflow,
+            IntErrorKind::NegOverflow => ParseIntError::NegOverflow,
+            IntErrorKind::Zero => ParseIntError::Zero,
+            _ => ParseIntError::Other,
+        }
+    }
+}
+
+crate::error_category! {
+    /// The error of parsing a float.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum ParseFloatError {
+        /// invalid float literal
+        Invalid,
+    }
+}
+
+impl From<core::num::ParseFloatError> for ParseFloatError {
+    fn from(_: core::num::ParseFloatError) -> ParseFloatError {
+        ParseFloatError::Invalid
+    }
+}
+
+crate::error_category! {
+    /// The error of a checked integer type conversion.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    #[non_exhaustive]
+    #[repr(u8)]
+    pub enum TryFromIntError {
+        /// out of range integral type conversion attempted
+        OutOfRange,
+    }
+}
+
+impl From<core::num::TryFromIntError> for TryFromIntError {
+    fn from(_: core::num::TryFromIntError) -> TryFromIntError {
+        TryFromIntError::OutOfRange
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{ChainError, DynError, Error, ErrorCategory, ERROR_CHAIN_LEN};
+use core::fmt::{self, Debug, Formatter};
+
+/// The context strings of all entries in an error chain, most recent first.
+type Contexts = [Option<&'static str>; ERROR_CHAIN_LEN + 1];
+
+/// A [`DynError`] with static context strings attached to the entries of its chain.
+///
+/// Error codes alone often lack information such as the register or file that was
+/// accessed when the error occurred. A [`ContextError`] stores up to one `&'static str`
+/// per chain entry alongside the [`DynError`], so no allocation is needed. Context is
+/// attached using [`DynError::context()`], [`ContextError::context()`] or
+/// [`ResultContext::context()`], and printed in parentheses after its entry when debug
+/// formatting the error:
+/// ```txt
+/// ControlTaskError(0): init failed
+/// - ICM20689Error(0): init failed (reading WHO_AM_I)
+/// - SpiError(0): bus error
+/// ```
+///
+/// Because the context strings are only stored in this separate type, [`Error`] and
+/// [`DynError`] keep their size if no context is used. For the same reason, chaining a
+/// `Result<T, ContextError>` with [`chain_err()`](crate::ResultChainError::chain_err())
+/// returns a plain [`Error`] without the context strings, while [`ContextError::chain()`]
+/// keeps them.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+/// }
+///
+/// fn read_register(_addr: u8) -> Result<u8, SpiError> {
+///     Err(SpiError::BusError)
+/// }
+///
+/// fn who_am_i() -> Result<u8, ContextError> {
+///     read_register(0x75).context("reading WHO_AM_I")
+/// }
+///
+/// let err = who_am_i().unwrap_err();
+/// assert_eq!(err.contexts()[0], Some("reading WHO_AM_I"));
+/// assert_eq!(format!("{:?}", err), "SpiError(0): BusError (reading WHO_AM_I)");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ContextError {
+    error: DynError,
+    contexts: Contexts,
+}
+
+impl ContextError {
+    /// Create a new [`ContextError`] from `error` without any context.
+    #[inline]
+    pub fn new(error: DynError) -> ContextError {
+        ContextError {
+            error,
+            contexts: [None; ERROR_CHAIN_LEN + 1],
+        }
+    }
+
+    /// Attach `context` to the most recent error, replacing any previous context of this
+    /// error.
+    #[inline]
+    pub fn context(mut self, context: &'static str) -> ContextError {
+        self.contexts[0] = Some(context);
+        self
+    }
+
+    /// Get the error without the context strings.
+    #[inline(always)]
+    pub fn error(&self) -> DynError {
+        self.error
+    }
+
+    /// Get the context strings of all entries in the error chain, most recent first.
+    #[inline]
+    pub fn contexts(&self) -> [Option<&'static str>; ERROR_CHAIN_LEN + 1] {
+        self.contexts
+    }
+
+    /// Query if this error was caused by `error_code` which belongs to the [error
+    /// category](ErrorCategory) `T`.
+    #[inline]
+    pub fn caused_by<T: ErrorCategory>(&self, error_code: T) -> bool {
+        self.error.caused_by(error_code)
+    }
+
+    /// Query the error code contained in this error that belongs to the [error
+    /// category](ErrorCategory) `T`. Return `None` if this error was not caused by the
+    /// specified error category.
+    #[inline]
+    pub fn code_of_category<T: ErrorCategory>(&self) -> Option<T> {
+        self.error.code_of_category()
+    }
+
+    /// Chain this error with the supplied `error_code` of [error category](ErrorCategory)
+    /// `O`, keeping the context strings of all previous entries.
+    ///
+    /// The new error has no context. If the error chain overflows, the contexts of the
+    /// dropped errors are lost together with them.
+    ///
+    /// ### Panics
+    /// Panics under the same conditions as [`DynError::chain()`](ChainError::chain()).
+    pub fn chain<O: ErrorCategory>(self, error_code: O) -> ContextError {
+        let error: DynError = self.error.chain(error_code).into();
+
+        // The chain keeps the most recent errors, so the contexts are shifted by one and
+        // only those of errors that are still in the chain are kept.
+        let mut contexts = [None; ERROR_CHAIN_LEN + 1];
+        for (context, old_context) in contexts[1..]
+            .iter_mut()
+            .zip(&self.contexts)
+            .take(error.chain_len())
+        {
+            *context = *old_context;
+        }
+
+        ContextError { error, contexts }
+    }
+}
+
+impl<O: ErrorCategory> ChainError<O, ContextError> for ContextError {
+    /// Chain this error with `error_code` and return a plain [`Error`] without the context
+    /// strings.
+    ///
+    /// This makes [`chain_err()`](crate::ResultChainError::chain_err()) work on a
+    /// `Result<T, ContextError>`, use the inherent [`ContextError::chain()`] to keep the
+    /// context strings.
+    ///
+    /// ### Panics
+    /// Panics under the same conditions as [`DynError::chain()`](ChainError::chain()).
+    #[inline]
+    fn chain(self, error_code: O) -> Error<O> {
+        self.error.chain(error_code)
+    }
+}
+
+impl DynError {
+    /// Attach the static string `context` to the most recent error of this error.
+    #[inline]
+    pub fn context(self, context: &'static str) -> ContextError {
+        ContextError::new(self).context(context)
+    }
+}
+
+/// A trait that allows attaching a static context string if a [`Result`] contains an
+/// error value.
+pub trait ResultContext<T> {
+    /// If the results contains an [`Err`] value, attach `context` to its most recent error
+    /// and return [`Err`] with the resulting [`ContextError`], otherwise forward the
+    /// [`Ok`] value.
+    fn context(self, context: &'static str) -> Result<T, ContextError>;
+}
+
+impl<T, E: Into<ContextError>> ResultContext<T> for Result<T, E> {
+    #[inline]
+    fn context(self, context: &'static str) -> Result<T, ContextError> {
+        match self {
+            Err(err) => Err(err.into().context(context)),
+            Ok(val) => Ok(val),
+        }
+    }
+}
+
+impl Debug for ContextError {
+    /// Debug format this error and its chain, with the context of every entry.
+    ///
+    /// Error message example:
+    /// ```txt
+    /// ControlTaskError(0): init failed
+    /// - ICM20689Error(0): init failed (reading WHO_AM_I)
+    /// - SpiError(0): bus error
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.error
+            .fmt_chain(f, None, |i, f| match self.contexts[i] {
+                Some(context) => write!(f, " ({})", context),
+                None => Ok(()),
+            })
+    }
+}
+
+impl From<DynError> for ContextError {
+    #[inline]
+    fn from(error: DynError) -> Self {
+        ContextError::new(error)
+    }
+}
+
+impl<C: ErrorCategory> From<Error<C>> for ContextError {
+    #[inline]
+    fn from(error: Error<C>) -> Self {
+        ContextError::new(error.into())
+    }
+}
+
+impl<C: ErrorCategory> From<C> for ContextError {
+    #[inline]
+    fn from(error: C) -> Self {
+        ContextError::new(DynError::new(error))
+    }
+}
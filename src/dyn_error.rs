@@ -1,6 +1,9 @@
+use crate::error_category::UNLINKED_INDEX;
+#[allow(unused_imports)]
+use crate::Unlinked;
 use crate::{
-    format_chained, ChainError, Error, ErrorCategory, ErrorCategoryHandle, ErrorCode,
-    ErrorCodeFormatter, ErrorData, ErrorIter, ERROR_CHAIN_LEN,
+    format_chained, ChainEntries, ChainEntry, ChainError, Error, ErrorCategory,
+    ErrorCategoryHandle, ErrorCode, ErrorCodeFormatter, ErrorData, ErrorIter, Payload,
 };
 use core::{fmt, ptr};
 
@@ -18,8 +21,11 @@ use core::{fmt, ptr};
 /// [`chain()`](ChainError::chain()) or [`chain_err()`](crate::ResultChainError::chain_err())
 /// with an different `error_code` cannot be checked at compile time. So instead of a compile
 /// error it will cause a panic if unlinked [error categories](ErrorCategory) are chained.
+/// [`try_chain()`](DynError::try_chain()) returns the error instead, and
+/// [`chain_or_unlinked()`](DynError::chain_or_unlinked()) records it as [`Unlinked`].
 ///
-/// ```
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[repr(u8)]
@@ -45,8 +51,15 @@ use core::{fmt, ptr};
 /// # do_chain();
 /// ```
 ///
-/// This will panic:
-/// ```should_panic
+/// This will panic (unless the feature `no-panic` is enabled):
+#[cfg_attr(
+    all(feature = "derive", not(feature = "no-panic")),
+    doc = "```should_panic"
+)]
+#[cfg_attr(
+    not(all(feature = "derive", not(feature = "no-panic"))),
+    doc = "```ignore"
+)]
 /// # use embedded_error_chain::prelude::*;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[repr(u8)]
@@ -75,7 +88,8 @@ use core::{fmt, ptr};
 /// an [`Error<BarError>`] value being returned. This is also true for
 /// [`chain_err()`](crate::ResultChainError::chain_err()).
 ///
-/// ```
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// # #[derive(Clone, Copy, ErrorCategory)]
 /// # #[repr(u8)]
@@ -105,7 +119,8 @@ use core::{fmt, ptr};
 /// implements the [`From`] trait for any [`Error<T>`](Error).
 ///
 /// The following would not work and won't compile:
-/// ```compile_fail
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// # #[derive(Clone, Copy, ErrorCategory)]
 /// # #[repr(u8)]
@@ -130,7 +145,7 @@ use core::{fmt, ptr};
 /// # do_chain();
 /// ```
 ///
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct DynError {
     error: ErrorData,
     category_formatter: ErrorCodeFormatter,
@@ -187,11 +202,75 @@ impl DynError {
         self.error.chain_len()
     }
 
+    /// Get the source of this error, which is the error chain without the most recent
+    /// error code, or `None` if the error chain is empty.
+    pub fn source(&self) -> Option<DynError> {
+        let mut data = self.error;
+        let fmt_index = data.pop_front()?;
+        match (self.category_formatter)(0, Some(fmt_index), None) {
+            (_, Ok(Some(formatter))) => Some(DynError::from_raw_parts(data, formatter.into())),
+            _ => None,
+        }
+    }
+
+    /// Shorten this error to at most `len` entries, keeping the most recent error code and
+    /// removing the oldest error codes of the error chain.
+    ///
+    /// The most recent error code is always kept, so a `len` of `0` has the same effect as
+    /// `1`. If `len` is greater or equal to the number of entries, nothing is changed.
+    pub fn truncate(&mut self, len: usize) {
+        self.error.truncate_chain(len.saturating_sub(1));
+    }
+
+    /// Append `source` with its error chain to the back of the error chain of this error.
+    ///
+    /// See [`Error::concat()`].
+    ///
+    /// ### Panics
+    /// If the oldest error code of this error is not linked with the category of `source`,
+    /// if the most recent error code of `source` is wide (which can't be chained), or if
+    /// the feature `panic-on-overflow` is enabled and error codes have to be dropped, this
+    /// function will panic. With the feature `no-panic`, the current error code of an
+    /// unlinked or wide `source` is recorded as [`Unlinked`] instead.
+    pub fn concat(&mut self, source: impl Into<DynError>) -> bool {
+        match self.try_concat(source) {
+            Ok(truncated) => truncated,
+            #[cfg(not(feature = "no-panic"))]
+            Err(source) if source.into_raw_parts().0.is_wide() => {
+                panic!("cannot concatenate a wide error code: {:?}", source)
+            }
+            #[cfg(not(feature = "no-panic"))]
+            Err(source) => panic!("cannot concatenate unlinked error categories: {:?}", source),
+            #[cfg(feature = "no-panic")]
+            Err(source) => self.error.concat_unlinked(source.into_raw_parts().0),
+        }
+    }
+
+    /// Try to append `source` with its error chain to the back of the error chain of this
+    /// error.
+    ///
+    /// See [`Error::try_concat()`].
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and error codes have to be dropped,
+    /// this function will panic.
+    pub fn try_concat(&mut self, source: impl Into<DynError>) -> Result<bool, DynError> {
+        let source = source.into();
+        let (source_data, source_formatter) = source.into_raw_parts();
+        match self.root_cause().link_index(source_formatter) {
+            Some(category_index) if !source_data.is_wide() => {
+                Ok(self.error.concat(source_data, category_index))
+            }
+            _ => Err(source),
+        }
+    }
+
     /// Get the capacity of the error chain.
     ///
-    /// Always returns [`ERROR_CHAIN_LEN`].
+    /// This is [`ERROR_CHAIN_LEN`](crate::ERROR_CHAIN_LEN), unless the most recent error code is wide (see
+    /// [`ErrorData::chain_capacity()`]).
     pub const fn chain_capacity(&self) -> usize {
-        ERROR_CHAIN_LEN
+        self.error.chain_capacity()
     }
 
     /// Get the [`ErrorCategoryHandle`] of the most recent error.
@@ -259,6 +338,28 @@ impl DynError {
         }
     }
 
+    /// Create an iterator that iterates over all entries in the error chain of this
+    /// error, starting with the most recent error code.
+    pub fn entries(&self) -> ChainEntries {
+        ChainEntries {
+            iter: self.iter(),
+            position: 0,
+        }
+    }
+
+    /// Get the oldest entry in the error chain of this error, which is the root cause of
+    /// this error.
+    ///
+    /// If the error chain is empty, this is the most recent error code.
+    pub fn root_cause(&self) -> ChainEntry {
+        // There is always at least one entry, the fallback avoids a panic path.
+        self.entries().last().unwrap_or(ChainEntry {
+            error_code: self.error.code(),
+            position: 0,
+            formatter: self.category_formatter,
+        })
+    }
+
     /// Try to chain this dynamically typed [`DynError`] with `error_code` of
     /// [error category](ErrorCategory) `C`.
     ///
@@ -288,6 +389,70 @@ impl DynError {
             })
             .ok_or(self)
     }
+
+    /// Chain this dynamically typed [`DynError`] with `error_code` of [error
+    /// category](ErrorCategory) `C`, even if the category of this error is not linked.
+    ///
+    /// If the category of this error is linked, this is the same as
+    /// [`chain()`](ChainError::chain()). Otherwise the error chain records the most recent
+    /// error code of this error as [`Unlinked`], which keeps the error chain but loses the
+    /// category of this error and of all its chained error codes. A wide error code also
+    /// loses its high 4 bits.
+    ///
+    /// This function doesn't panic because of unlinked categories. If the error chain
+    /// overflows, it behaves like [`ErrorData::chain()`]: the oldest error code is lost, or
+    /// with the feature `panic-on-overflow` (and without `no-panic`) this function panics.
+    ///
+    /// ### Lost categories
+    /// An [`Unlinked`] entry only keeps the error code. The categories of this error and
+    /// of all errors chained before it are lost, so these entries are printed as
+    /// `<unlinked>` and can no longer be queried using their categories, e.g. with
+    /// [`caused_by()`](Error::caused_by()). To keep them, chain the error as an
+    /// [`ErrorTree`](crate::ErrorTree) instead, whose nodes don't have to be linked:
+    /// `ErrorTree::<2>::from(err).chain(error_code)`.
+    ///
+    #[cfg_attr(feature = "derive", doc = "```")]
+    #[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+    /// # use embedded_error_chain::prelude::*;
+    /// use embedded_error_chain::Unlinked;
+    ///
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[repr(u8)]
+    /// enum FooError {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[repr(u8)]
+    /// enum BarError {
+    ///     Failed,
+    /// }
+    ///
+    /// let err = DynError::from(FooError::Second).chain_or_unlinked(BarError::Failed);
+    /// assert_eq!(err.chain_len(), 1);
+    /// assert!(err.caused_by(Unlinked(1)));
+    /// assert_eq!(
+    ///     format!("{:?}", err),
+    ///     "BarError(0): Failed\n- <unlinked>(1): unknown error"
+    /// );
+    /// ```
+    pub fn chain_or_unlinked<C: ErrorCategory>(self, error_code: C) -> Error<C> {
+        let category_index = C::chainable_category_formatters()
+            .iter()
+            .position(|formatter| {
+                ptr::eq(
+                    *formatter as *const (),
+                    self.category_formatter as *const (),
+                )
+            })
+            .map_or(UNLINKED_INDEX, |i| i as u8);
+
+        let mut data: ErrorData = self.error;
+        data.strip_wide();
+        ErrorData::chain(&mut data, error_code.into(), category_index);
+        Error::from_raw(data)
+    }
 }
 
 impl<O: ErrorCategory> ChainError<O, DynError> for DynError {
@@ -302,9 +467,26 @@ impl<O: ErrorCategory> ChainError<O, DynError> for DynError {
     /// returned by
     /// [`O::chainable_category_formatters()`](ErrorCategory::chainable_category_formatters())
     /// does **not** contain [`self.formatter()`](DynError::formatter()).
+    ///
+    /// ### With the feature `no-panic`
+    /// This function doesn't panic, but behaves like
+    /// [`chain_or_unlinked()`](DynError::chain_or_unlinked()) instead: an unlinked
+    /// category is silently recorded as an [`Unlinked`] entry, which loses the names of
+    /// the categories of this error. Because cargo features are additive, this happens as
+    /// soon as any crate in the dependency graph enables `no-panic`. Unlike
+    /// [`Error::chain()`](ChainError::chain()), whose links are checked at compile time,
+    /// a [`DynError`] can only be checked at runtime, so call
+    /// [`try_chain()`](DynError::try_chain()) or
+    /// [`chain_or_unlinked()`](DynError::chain_or_unlinked()) if unlinked categories are
+    /// possible, to handle them the same way regardless of the enabled features.
     fn chain(self, error_code: O) -> Error<O> {
-        self.try_chain(error_code)
-            .expect("cannot chain unlinked error categories")
+        #[cfg(not(feature = "no-panic"))]
+        match self.try_chain(error_code) {
+            Ok(error) => error,
+            Err(error) => panic!("cannot chain unlinked error categories: {:?}", error),
+        }
+        #[cfg(feature = "no-panic")]
+        self.chain_or_unlinked(error_code)
     }
 }
 
@@ -318,16 +500,41 @@ impl fmt::Debug for DynError {
     /// - SpiError(0): bus error
     /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (_, fmt_result) =
-            (self.category_formatter)(self.code(), self.error.first_formatter_index(), Some(f));
+        self.fmt_chain(f, None, |_, _| Ok(()))
+    }
+}
+
+impl DynError {
+    /// Debug format this error and its chain, and call `entry_suffix` with the position
+    /// of every chain entry (`0` is the most recent error) right after it was written.
+    ///
+    /// If `fmt_first` is `Some`, it is used to format the most recent error instead of
+    /// its formatter function.
+    pub(crate) fn fmt_chain(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        fmt_first: Option<&dyn Fn(&mut fmt::Formatter<'_>) -> fmt::Result>,
+        mut entry_suffix: impl FnMut(usize, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        let next_fmt_index = self.error.first_formatter_index();
+        let (_, fmt_result) = match fmt_first {
+            Some(fmt_first) => {
+                fmt_first(f)?;
+                (self.category_formatter)(self.code(), next_fmt_index, None)
+            }
+            None => (self.category_formatter)(self.code(), next_fmt_index, Some(f)),
+        };
 
         let mut formatter_func = fmt_result?;
-        for (ec, next_fmt_index) in self.error.iter_chain() {
+        entry_suffix(0, f)?;
+        for (i, (ec, next_fmt_index)) in self.error.iter_chain().enumerate() {
             formatter_func = if let Some(formatter_func) = formatter_func {
                 write!(f, "\n- ")?;
                 let (_, next_formatter) = formatter_func.into()(ec, next_fmt_index, Some(f));
 
-                next_formatter?
+                let next_formatter = next_formatter?;
+                entry_suffix(i + 1, f)?;
+                next_formatter
             } else {
                 break;
             };
@@ -336,9 +543,10 @@ impl fmt::Debug for DynError {
     }
 }
 
-impl<C: ErrorCategory> From<Error<C>> for DynError {
+impl<C: ErrorCategory, P: Payload> From<Error<C, P>> for DynError {
+    /// Convert `error` into a [`DynError`], its payload is lost.
     #[inline]
-    fn from(error: crate::Error<C>) -> Self {
+    fn from(error: crate::Error<C, P>) -> Self {
         DynError::from_raw_parts(error.into(), format_chained::<C>)
     }
 }
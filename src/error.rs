@@ -1,7 +1,8 @@
 use crate::{
     error_category::{self, ErrorCodeFormatter},
     error_data::ErrorDataChainIter,
-    marker, DynError, ErrorCategory, ErrorCategoryHandle, ErrorCode, ErrorData, ERROR_CHAIN_LEN,
+    marker, DynError, ErrorCategory, ErrorCategoryHandle, ErrorCode, ErrorData, FromCategory,
+    Payload, PayloadCategory,
 };
 use core::marker::PhantomData;
 use core::{
@@ -27,7 +28,8 @@ use core::{
 /// is done using the `links` argument in the `error_category` attribute, if
 /// [`ErrorCategory`] is implemented using the derive macro.
 ///
-/// ```
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[repr(u8)]
@@ -52,7 +54,8 @@ use core::{
 /// ```
 ///
 /// This does not compile:
-/// ```compile_fail
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[repr(u8)]
@@ -91,7 +94,8 @@ use core::{
 /// being directly forwarded to the caller, you must have a single error of indirection in
 /// between.
 ///
-/// ```
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[repr(u8)]
@@ -119,8 +123,36 @@ use core::{
 /// If you want to directly forward a single or multiple source errors with different
 /// unrelated [error categories](ErrorCategory) and you don't need the advantages outlined
 /// above use [`DynError`] instead.
+///
+/// ## Payload
+/// The optional type parameter `P` is a small [`Payload`] value (such as a register
+/// address or measured value) attached to the most recent error code. It is printed using
+/// the `{value}` placeholder of the `#[error(...)]` attribute, see
+/// [`ErrorCategory::fmt_with_value()`]. The payload is lost when the error is chained or
+/// converted into a [`DynError`]. With the default payload `()` the size guarantee above
+/// still holds: `Error<C>` is `#[repr(transparent)]` over [`ErrorData`] and so has the
+/// same layout and ABI as a [`u32`].
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum GyroAccError {
+///     #[error("{variant} (value={value})")]
+///     InvalidValue,
+/// }
+///
+/// fn check_value(value: u16) -> Result<(), Error<GyroAccError, u16>> {
+///     Err(Error::with_payload(GyroAccError::InvalidValue, value))
+/// }
+///
+/// let err = check_value(300).unwrap_err();
+/// assert_eq!(err.payload(), 300);
+/// assert_eq!(format!("{:?}", err), "GyroAccError(0): InvalidValue (value=300)");
+/// ```
 #[repr(transparent)]
-pub struct Error<C>(ErrorData, PhantomData<C>);
+pub struct Error<C, P: Payload = ()>(P::Storage, PhantomData<C>);
 
 impl<C> Error<C> {
     /// Create a new [`Error`] with an empty chain from the supplied raw `error_code`.
@@ -141,14 +173,25 @@ impl<C> Error<C> {
     pub const fn from_raw(error_data: ErrorData) -> Error<C> {
         Error(error_data, PhantomData)
     }
-}
 
-impl<C> Error<C> {
     /// Get the capacity of the error chain.
     ///
-    /// Always returns [`ERROR_CHAIN_LEN`].
+    /// This is [`ERROR_CHAIN_LEN`](crate::ERROR_CHAIN_LEN), unless the most recent error code is wide (see
+    /// [`ErrorData::chain_capacity()`]).
     pub const fn chain_capacity(&self) -> usize {
-        ERROR_CHAIN_LEN
+        self.0.chain_capacity()
+    }
+}
+
+impl<C, P: Payload> Error<C, P> {
+    #[inline(always)]
+    fn data(&self) -> ErrorData {
+        P::load(&self.0).0
+    }
+
+    #[inline(always)]
+    fn data_mut(&mut self) -> &mut ErrorData {
+        P::data_mut(&mut self.0)
     }
 }
 
@@ -158,16 +201,228 @@ impl<C: ErrorCategory> Error<C> {
     pub fn new(error_code: C) -> Error<C> {
         Error(ErrorData::new(error_code.into()), PhantomData)
     }
+}
+
+impl<C: PayloadCategory> Error<C, C::Payload> {
+    /// Create a new [`Error`] with an empty chain from the supplied `variant`, and store
+    /// its field as the payload.
+    #[inline(always)]
+    pub fn from_variant(variant: C) -> Error<C, C::Payload> {
+        Error::with_payload(variant, variant.payload())
+    }
+
+    /// Get the variant of the latest error with its field reconstructed from the payload.
+    #[inline]
+    pub fn variant(&self) -> C {
+        C::from_payload(self.code().into(), self.payload())
+    }
+}
+
+impl<C: ErrorCategory, P: Payload> Error<C, P> {
+    /// Create a new [`Error`] with an empty chain from the supplied `error_code` and
+    /// attach `payload` to it.
+    #[inline(always)]
+    pub fn with_payload(error_code: C, payload: P) -> Error<C, P> {
+        Error(
+            P::store(ErrorData::new(error_code.into()), payload),
+            PhantomData,
+        )
+    }
+
+    /// Get the payload of the latest error.
+    #[inline(always)]
+    pub fn payload(&self) -> P {
+        P::load(&self.0).1
+    }
+
+    /// Get this error without its payload.
+    #[inline(always)]
+    pub fn without_payload(&self) -> Error<C> {
+        Error(self.data(), PhantomData)
+    }
 
     /// Get the error code of the latest error.
     #[inline]
     pub fn code(&self) -> C {
-        self.0.code().into()
+        self.data().code().into()
+    }
+
+    /// Get the error code of the latest error as it is stored in this error.
+    ///
+    /// Unlike [`code()`](Error::code()), this returns the original value of an error code
+    /// that is not a valid variant of `C` (see `#[error(unknown)]` in
+    /// [`ErrorCategory`](derive@crate::ErrorCategory)).
+    #[inline]
+    pub fn raw_code(&self) -> ErrorCode {
+        self.data().code()
+    }
+
+    /// Replace the error code of the latest error with `error_code` and return the old
+    /// one.
+    ///
+    /// The error chain and the payload are kept unchanged, unless a wide `error_code`
+    /// replaces a narrow one while the chain is full (see [`ErrorData::set_code()`]).
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and the oldest error code of a full
+    /// chain has to be dropped to make room for a wide `error_code`, this function will
+    /// panic.
+    #[inline]
+    pub fn set_code(&mut self, error_code: C) -> C {
+        self.data_mut().set_code(error_code.into()).into()
     }
 
     /// Get the length of the error chain.
     pub fn chain_len(&self) -> usize {
-        self.0.chain_len()
+        self.data().chain_len()
+    }
+
+    /// Convert this error into an error of the [error category](ErrorCategory) `D` by
+    /// replacing its most recent error code, while keeping the error chain.
+    ///
+    /// See [`FromCategory`] for how the error code is converted. The payload of this error
+    /// is lost.
+    pub fn map_category<D: FromCategory<C>>(self) -> Error<D> {
+        let mut data = self.data();
+        data.set_code(D::from_category(self.code()).into());
+        Error::from_raw(data)
+    }
+
+    /// Get the source of this error, which is the error chain without the most recent
+    /// error code, or `None` if the error chain is empty.
+    ///
+    #[cfg_attr(feature = "derive", doc = "```")]
+    #[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+    /// # use embedded_error_chain::prelude::*;
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[repr(u8)]
+    /// enum SpiError {
+    ///     BusError,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[error_category(links(SpiError))]
+    /// #[repr(u8)]
+    /// enum GyroAccError {
+    ///     ReadoutFailed,
+    /// }
+    ///
+    /// let err = SpiError::BusError.chain(GyroAccError::ReadoutFailed);
+    /// let source = err.source().unwrap();
+    /// assert!(source.is::<SpiError>());
+    /// assert!(source.source().is_none());
+    /// ```
+    pub fn source(&self) -> Option<DynError> {
+        let mut data = self.data();
+        let fmt_index = data.pop_front()?;
+        match error_category::format_chained::<C>(0, Some(fmt_index), None) {
+            (_, Ok(Some(formatter))) => Some(DynError::from_raw_parts(data, formatter.into())),
+            _ => None,
+        }
+    }
+
+    /// Split this error into its most recent error code and its
+    /// [source](Error::source()).
+    ///
+    /// This allows layered code to remove its own error code and chain the source with a
+    /// different one. The payload of this error is lost.
+    pub fn split_first(self) -> (C, Option<DynError>) {
+        (self.code(), self.source())
+    }
+
+    /// Shorten this error to at most `len` entries, keeping the most recent error code and
+    /// removing the oldest error codes of the error chain.
+    ///
+    /// The most recent error code is always kept, so a `len` of `0` has the same effect as
+    /// `1`. If `len` is greater or equal to the number of entries, nothing is changed.
+    pub fn truncate(&mut self, len: usize) {
+        self.data_mut().truncate_chain(len.saturating_sub(1));
+    }
+
+    /// Append `source` with its error chain to the back of the error chain of this error.
+    ///
+    /// This combines an error that was built as context elsewhere with the error that
+    /// caused it. The oldest error code of this error must belong to an [error
+    /// category](ErrorCategory) that is linked to the category of the most recent error
+    /// code of `source`.
+    ///
+    /// Returns `true` if error codes of `source` had to be dropped because they did not fit
+    /// into the error chain (see [`ERROR_CHAIN_LEN`](crate::ERROR_CHAIN_LEN)). The oldest error codes are dropped
+    /// first.
+    ///
+    #[cfg_attr(feature = "derive", doc = "```")]
+    #[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+    /// # use embedded_error_chain::prelude::*;
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[repr(u8)]
+    /// enum SpiError {
+    ///     BusError,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[error_category(links(SpiError))]
+    /// #[repr(u8)]
+    /// enum GyroAccError {
+    ///     ReadoutFailed,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[error_category(links(GyroAccError))]
+    /// #[repr(u8)]
+    /// enum ImuError {
+    ///     CalibrationFailed,
+    /// }
+    ///
+    /// let mut err = GyroAccError::ReadoutFailed.chain(ImuError::CalibrationFailed);
+    /// assert!(!err.concat(SpiError::BusError));
+    /// assert_eq!(
+    ///     format!("{:?}", err),
+    ///     "ImuError(0): CalibrationFailed\n\
+    ///      - GyroAccError(0): ReadoutFailed\n\
+    ///      - SpiError(0): BusError"
+    /// );
+    /// ```
+    ///
+    /// ### Panics
+    /// If the oldest error code of this error is not linked with the category of `source`,
+    /// if the most recent error code of `source` is wide (which can't be chained), or if
+    /// the feature `panic-on-overflow` is enabled and error codes have to be dropped, this
+    /// function will panic. With the feature `no-panic`, the current error code of an
+    /// unlinked or wide `source` is recorded as [`Unlinked`](crate::Unlinked) instead
+    /// (see [`DynError::chain_or_unlinked()`]).
+    pub fn concat(&mut self, source: impl Into<DynError>) -> bool {
+        match self.try_concat(source) {
+            Ok(truncated) => truncated,
+            #[cfg(not(feature = "no-panic"))]
+            Err(source) if source.into_raw_parts().0.is_wide() => {
+                panic!("cannot concatenate a wide error code: {:?}", source)
+            }
+            #[cfg(not(feature = "no-panic"))]
+            Err(source) => panic!("cannot concatenate unlinked error categories: {:?}", source),
+            #[cfg(feature = "no-panic")]
+            Err(source) => self.data_mut().concat_unlinked(source.into_raw_parts().0),
+        }
+    }
+
+    /// Try to append `source` with its error chain to the back of the error chain of this
+    /// error.
+    ///
+    /// Same as [`concat()`](Error::concat()), but returns `source` as [`Err`] instead of
+    /// panicking if the oldest error code of this error is not linked with the category of
+    /// `source`, or if the most recent error code of `source` is wide.
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and error codes have to be dropped,
+    /// this function will panic.
+    pub fn try_concat(&mut self, source: impl Into<DynError>) -> Result<bool, DynError> {
+        let source = source.into();
+        let (source_data, source_formatter) = source.into_raw_parts();
+        match self.root_cause().link_index(source_formatter) {
+            Some(category_index) if !source_data.is_wide() => {
+                Ok(self.data_mut().concat(source_data, category_index))
+            }
+            _ => Err(source),
+        }
     }
 
     /// Query if this error was caused by `error_code` which belongs to the error category
@@ -197,11 +452,33 @@ impl<C: ErrorCategory> Error<C> {
     pub fn iter(&self) -> ErrorIter {
         ErrorIter {
             formatter_func: Some(error_category::format_chained::<C>),
-            curr_error_code: self.0.code(),
-            next_formatter_index: self.0.first_formatter_index(),
-            chain_iter: self.0.iter_chain(),
+            curr_error_code: self.data().code(),
+            next_formatter_index: self.data().first_formatter_index(),
+            chain_iter: self.data().iter_chain(),
         }
     }
+
+    /// Create an iterator that iterates over all entries in the error chain of this
+    /// error, starting with the most recent error code.
+    pub fn entries(&self) -> ChainEntries {
+        ChainEntries {
+            iter: self.iter(),
+            position: 0,
+        }
+    }
+
+    /// Get the oldest entry in the error chain of this error, which is the root cause of
+    /// this error.
+    ///
+    /// If the error chain is empty, this is the most recent error code.
+    pub fn root_cause(&self) -> ChainEntry {
+        // There is always at least one entry, the fallback avoids a panic path.
+        self.entries().last().unwrap_or(ChainEntry {
+            error_code: self.data().code(),
+            position: 0,
+            formatter: error_category::format_chained::<C>,
+        })
+    }
 }
 
 /// An iterator over all error codes in this [`Error`].
@@ -217,10 +494,10 @@ pub struct ErrorIter {
     pub(crate) chain_iter: ErrorDataChainIter,
 }
 
-impl Iterator for ErrorIter {
-    type Item = (ErrorCode, ErrorCategoryHandle);
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl ErrorIter {
+    /// Advance to the next error code and return it together with the
+    /// [`ErrorCodeFormatter`] and [`ErrorCategoryHandle`] of its category.
+    fn next_entry(&mut self) -> Option<(ErrorCode, ErrorCodeFormatter, ErrorCategoryHandle)> {
         if let Some(formatter_func) = self.formatter_func {
             let (err_cat_handle, next_formatter_res) =
                 formatter_func(0, self.next_formatter_index.take(), None);
@@ -236,20 +513,142 @@ impl Iterator for ErrorIter {
                 self.formatter_func = None;
             }
 
-            Some((error_code, err_cat_handle))
+            Some((error_code, formatter_func, err_cat_handle))
         } else {
             None
         }
     }
 }
+
+impl Iterator for ErrorIter {
+    type Item = (ErrorCode, ErrorCategoryHandle);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_entry()
+            .map(|(error_code, _, err_cat_handle)| (error_code, err_cat_handle))
+    }
+}
 impl FusedIterator for ErrorIter {}
 
-impl<C: ErrorCategory> Debug for Error<C> {
+/// A single entry in the error chain of an [`Error`] or [`DynError`](crate::DynError).
+///
+/// Debug and display formatting an entry only formats this entry, using the same format as
+/// the entries of a formatted [`Error`]:
+/// ```txt
+/// {category name}({error code}): {error code description}
+/// ```
+#[derive(Clone, Copy)]
+pub struct ChainEntry {
+    pub(crate) error_code: ErrorCode,
+    pub(crate) position: usize,
+    pub(crate) formatter: ErrorCodeFormatter,
+}
+
+impl ChainEntry {
+    /// Get the raw error code of this entry.
+    #[inline]
+    pub fn code(&self) -> ErrorCode {
+        self.error_code
+    }
+
+    /// Get the position of this entry in the error chain.
+    ///
+    /// The most recent error code has position `0`, its cause position `1` and so on.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get a handle to the [`ErrorCategory`] of this entry.
+    #[inline]
+    pub fn category(&self) -> ErrorCategoryHandle {
+        (self.formatter)(0, None, None).0
+    }
+
+    /// Query if this entry belongs to the [`ErrorCategory`] `T`.
+    #[inline]
+    pub fn is<T: ErrorCategory>(&self) -> bool {
+        self.category().is_handle_of::<T>()
+    }
+
+    /// Get the index of `formatter` in the slice of chainable formatters of this entry's
+    /// [`ErrorCategory`], or `None` if the category of `formatter` is not linked.
+    pub(crate) fn link_index(&self, formatter: ErrorCodeFormatter) -> Option<u8> {
+        (0..6).find(|&index| match (self.formatter)(0, Some(index), None).1 {
+            Ok(Some(next_formatter)) => {
+                next_formatter.into() as *const () == formatter as *const ()
+            }
+            _ => false,
+        })
+    }
+
+    /// Get the error code of this entry as `T` if it belongs to the [`ErrorCategory`] `T`,
+    /// otherwise return `None`.
+    #[inline]
+    pub fn downcast<T: ErrorCategory>(&self) -> Option<T> {
+        if self.is::<T>() {
+            Some(self.error_code.into())
+        } else {
+            None
+        }
+    }
+}
+
+impl Debug for ChainEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.formatter)(self.error_code, None, Some(f)).1?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for ChainEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// An iterator over all entries in the error chain of an [`Error`] or
+/// [`DynError`](crate::DynError), starting with the most recent error code.
+pub struct ChainEntries {
+    pub(crate) iter: ErrorIter,
+    pub(crate) position: usize,
+}
+
+impl Iterator for ChainEntries {
+    type Item = ChainEntry;
+
+    fn next(&mut self) -> Option<ChainEntry> {
+        self.iter.next_entry().map(|(error_code, formatter, _)| {
+            let position = self.position;
+            self.position = position.saturating_add(1);
+            ChainEntry {
+                error_code,
+                position,
+                formatter,
+            }
+        })
+    }
+}
+impl FusedIterator for ChainEntries {}
+
+impl<C: ErrorCategory, P: Payload> Debug for Error<C, P> {
     /// Debug format this error and its chain.
     ///
-    /// Delegates to [`DynError::fmt()`].
+    /// Delegates to [`DynError::fmt()`], the payload is formatted using
+    /// [`ErrorCategory::fmt_with_value()`].
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        DynError::from(*self).fmt(f)
+        let error = DynError::from(self.without_payload());
+        match self.payload().value() {
+            Some(value) => error.fmt_chain(
+                f,
+                Some(&|f: &mut Formatter<'_>| {
+                    write!(f, "{}({}): ", C::NAME, self.data().code())?;
+                    self.code().fmt_with_value(value, f)
+                }),
+                |_, _| Ok(()),
+            ),
+            None => error.fmt(f),
+        }
     }
 }
 
@@ -276,13 +675,87 @@ pub trait ResultChainError<T, O: ErrorCategory, Tag> {
     fn chain_err(self, error_code: O) -> Result<T, Error<O>>;
 }
 
+/// A trait that allows converting a [`None`] value of an [`Option`] into an [`Error`].
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum ParseError {
+///     MissingField,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(ParseError))]
+/// #[repr(u8)]
+/// enum ConfigError {
+///     InvalidBaudRate,
+/// }
+///
+/// fn parse_baud_rate(line: &str) -> Result<u32, Error<ConfigError>> {
+///     let value = line
+///         .split('=')
+///         .nth(1)
+///         .ok_or_chain(ParseError::MissingField, ConfigError::InvalidBaudRate)?;
+///     value
+///         .trim()
+///         .parse()
+///         .ok()
+///         .ok_or_err(ConfigError::InvalidBaudRate)
+/// }
+///
+/// assert_eq!(parse_baud_rate("baud = 9600"), Ok(9600));
+/// assert!(parse_baud_rate("baud").unwrap_err().caused_by(ParseError::MissingField));
+/// ```
+pub trait OptionChainError<T> {
+    /// If the option is [`None`], return [`Err`] with a new [`Error`] of the supplied
+    /// `error_code`, otherwise return [`Ok`] with the [`Some`] value.
+    fn ok_or_err<C: ErrorCategory>(self, error_code: C) -> Result<T, Error<C>>;
+
+    /// If the option is [`None`], return [`Err`] with a new [`Error`] of the error code
+    /// returned by `f`, otherwise return [`Ok`] with the [`Some`] value.
+    ///
+    /// `f` is only called if the option is [`None`].
+    fn ok_or_else_err<C: ErrorCategory, F: FnOnce() -> C>(self, f: F) -> Result<T, Error<C>>;
+
+    /// If the option is [`None`], chain `cause` with the supplied `error_code` and return
+    /// [`Err`] with the result, otherwise return [`Ok`] with the [`Some`] value.
+    ///
+    /// ### Panics
+    /// If the option is [`None`] and the [error category](ErrorCategory) `O` is not linked
+    /// with the [`ErrorCategory`] of the most recent error code of `cause`, this function
+    /// will panic.
+    fn ok_or_chain<O: ErrorCategory, Tag, E: ChainError<O, Tag>>(
+        self,
+        cause: E,
+        error_code: O,
+    ) -> Result<T, Error<O>>;
+
+    /// If the option is [`None`], chain the cause returned by `f` with the supplied
+    /// `error_code` and return [`Err`] with the result, otherwise return [`Ok`] with the
+    /// [`Some`] value.
+    ///
+    /// `f` is only called if the option is [`None`].
+    ///
+    /// ### Panics
+    /// If the option is [`None`] and the [error category](ErrorCategory) `O` is not linked
+    /// with the [`ErrorCategory`] of the most recent error code of the cause, this
+    /// function will panic.
+    fn ok_or_else_chain<O: ErrorCategory, Tag, E: ChainError<O, Tag>, F: FnOnce() -> E>(
+        self,
+        f: F,
+        error_code: O,
+    ) -> Result<T, Error<O>>;
+}
+
 macro_rules! impl_chain_error {
     ($([$t:ident, $idx:literal]),*) => {
         $(
-            impl<C: ErrorCategory> ChainError<C, (marker::$t, marker::Error_t)> for Error<C::$t> {
+            impl<C: ErrorCategory, P: Payload> ChainError<C, (marker::$t, marker::Error_t)> for Error<C::$t, P> {
                 #[inline(always)]
                 fn chain(self, error_code: C) -> Error<C> {
-                    let mut data: ErrorData = self.0;
+                    let mut data = self.data();
                     ErrorData::chain(&mut data, error_code.into(), $idx);
                     Error(data, PhantomData)
                 }
@@ -314,26 +787,205 @@ where
     }
 }
 
-impl<C: ErrorCategory> PartialEq for Error<C> {
-    fn eq(&self, other: &Error<C>) -> bool {
-        self.0 == other.0
+impl<T> OptionChainError<T> for Option<T> {
+    #[inline]
+    fn ok_or_err<C: ErrorCategory>(self, error_code: C) -> Result<T, Error<C>> {
+        match self {
+            Some(val) => Ok(val),
+            None => Err(Error::new(error_code)),
+        }
+    }
+
+    #[inline]
+    fn ok_or_else_err<C: ErrorCategory, F: FnOnce() -> C>(self, f: F) -> Result<T, Error<C>> {
+        match self {
+            Some(val) => Ok(val),
+            None => Err(Error::new(f())),
+        }
+    }
+
+    #[inline]
+    fn ok_or_chain<O: ErrorCategory, Tag, E: ChainError<O, Tag>>(
+        self,
+        cause: E,
+        error_code: O,
+    ) -> Result<T, Error<O>> {
+        match self {
+            Some(val) => Ok(val),
+            None => Err(cause.chain(error_code)),
+        }
+    }
+
+    #[inline]
+    fn ok_or_else_chain<O: ErrorCategory, Tag, E: ChainError<O, Tag>, F: FnOnce() -> E>(
+        self,
+        f: F,
+        error_code: O,
+    ) -> Result<T, Error<O>> {
+        match self {
+            Some(val) => Ok(val),
+            None => Err(f().chain(error_code)),
+        }
     }
 }
-impl<C: ErrorCategory> Eq for Error<C> {}
 
-impl<C: ErrorCategory> Clone for Error<C> {
-    #[inline(always)]
-    fn clone(&self) -> Self {
-        Error(self.0, PhantomData)
+/// A trait with combinators for a [`Result`] that contains an [`Error`] value.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// # use embedded_error_chain::ResultErrorExt;
+/// #[derive(Clone, Copy, PartialEq, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+///     Timeout,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum SensorError {
+///     Retryable,
+///     Fatal,
+/// }
+///
+/// fn spi_read(error_code: SpiError) -> Result<u8, Error<SpiError>> {
+///     Err(Error::new(error_code))
+/// }
+///
+/// fn sensor_read(error_code: SpiError) -> Result<u8, Error<SensorError>> {
+///     spi_read(error_code).chain_err_with(|err| match err.code() {
+///         SpiError::BusError => SensorError::Retryable,
+///         _ => SensorError::Fatal,
+///     })
+/// }
+///
+/// assert_eq!(
+///     sensor_read(SpiError::BusError).unwrap_err().code(),
+///     SensorError::Retryable
+/// );
+/// assert_eq!(
+///     sensor_read(SpiError::Timeout).unwrap_err().code(),
+///     SensorError::Fatal
+/// );
+///
+/// // Fall back to a default value on timeouts.
+/// let val = spi_read(SpiError::Timeout)
+///     .inspect_chain(|err| println!("spi read failed: {:?}", err))
+///     .or_else_recover(|err| match err.code() {
+///         SpiError::Timeout => Some(0),
+///         _ => None,
+///     });
+/// assert_eq!(val, Ok(0));
+/// ```
+pub trait ResultErrorExt<T, C: ErrorCategory, P: Payload>: Sized {
+    /// If the result contains an [`Err`] value, chain it with the error code returned by
+    /// `f` and return [`Err`] with the result, otherwise forward the [`Ok`] value.
+    ///
+    /// `f` is only called if the result is an [`Err`] value, and receives the error so
+    /// that the new error code can depend on it.
+    ///
+    /// ### Panics
+    /// If this [`Result`] is an [`Err`] value and the [error category](ErrorCategory) `O`
+    /// is not linked with the [`ErrorCategory`] `C`, this function will panic.
+    fn chain_err_with<O, Tag, F>(self, f: F) -> Result<T, Error<O>>
+    where
+        O: ErrorCategory,
+        Error<C, P>: ChainError<O, Tag>,
+        F: FnOnce(&Error<C, P>) -> O;
+
+    /// If the result contains an [`Err`] value, replace the error code of its latest error
+    /// with the one returned by `f`, otherwise forward the [`Ok`] value.
+    ///
+    /// The error chain and payload of the error are kept unchanged (see
+    /// [`Error::set_code()`]).
+    fn map_code<F: FnOnce(C) -> C>(self, f: F) -> Result<T, Error<C, P>>;
+
+    /// If the result contains an [`Err`] value, convert it into an error of the [error
+    /// category](ErrorCategory) `D` using [`Error::map_category()`], otherwise forward the
+    /// [`Ok`] value.
+    fn map_category<D: FromCategory<C>>(self) -> Result<T, Error<D>>;
+
+    /// If the result contains an [`Err`] value, call `f` with it and return [`Ok`] with
+    /// the value returned by `f` if it is [`Some`], otherwise forward the result
+    /// unchanged.
+    ///
+    /// This allows recovering from specific error codes while forwarding all others.
+    fn or_else_recover<F: FnOnce(&Error<C, P>) -> Option<T>>(self, f: F) -> Result<T, Error<C, P>>;
+
+    /// If the result contains an [`Err`] value, call `f` with a reference to it, then
+    /// forward the result unchanged.
+    ///
+    /// This is useful for logging errors before they are chained or returned.
+    fn inspect_chain<F: FnOnce(&Error<C, P>)>(self, f: F) -> Result<T, Error<C, P>>;
+}
+
+impl<T, C: ErrorCategory, P: Payload> ResultErrorExt<T, C, P> for Result<T, Error<C, P>> {
+    #[inline]
+    fn chain_err_with<O, Tag, F>(self, f: F) -> Result<T, Error<O>>
+    where
+        O: ErrorCategory,
+        Error<C, P>: ChainError<O, Tag>,
+        F: FnOnce(&Error<C, P>) -> O,
+    {
+        match self {
+            Err(err) => {
+                let error_code = f(&err);
+                Err(err.chain(error_code))
+            }
+            Ok(val) => Ok(val),
+        }
+    }
+
+    #[inline]
+    fn map_code<F: FnOnce(C) -> C>(self, f: F) -> Result<T, Error<C, P>> {
+        match self {
+            Err(mut err) => {
+                err.set_code(f(err.code()));
+                Err(err)
+            }
+            Ok(val) => Ok(val),
+        }
     }
 
+    #[inline]
+    fn map_category<D: FromCategory<C>>(self) -> Result<T, Error<D>> {
+        self.map_err(Error::map_category)
+    }
+
+    #[inline]
+    fn or_else_recover<F: FnOnce(&Error<C, P>) -> Option<T>>(self, f: F) -> Result<T, Error<C, P>> {
+        match self {
+            Err(err) => f(&err).ok_or(err),
+            Ok(val) => Ok(val),
+        }
+    }
+
+    #[inline]
+    fn inspect_chain<F: FnOnce(&Error<C, P>)>(self, f: F) -> Result<T, Error<C, P>> {
+        if let Err(err) = &self {
+            f(err);
+        }
+        self
+    }
+}
+
+impl<C: ErrorCategory, P: Payload> PartialEq for Error<C, P> {
+    fn eq(&self, other: &Error<C, P>) -> bool {
+        self.0 == other.0
+    }
+}
+impl<C: ErrorCategory, P: Payload> Eq for Error<C, P> {}
+
+impl<C: ErrorCategory, P: Payload> Clone for Error<C, P> {
     #[inline(always)]
-    fn clone_from(&mut self, source: &Self) {
-        self.0 = source.0;
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-impl<C: ErrorCategory> Copy for Error<C> {}
+impl<C: ErrorCategory, P: Payload> Copy for Error<C, P> {}
 
 impl<C: ErrorCategory> From<C> for Error<C> {
     #[inline(always)]
@@ -342,9 +994,9 @@ impl<C: ErrorCategory> From<C> for Error<C> {
     }
 }
 
-impl<C: ErrorCategory> From<Error<C>> for ErrorData {
+impl<C: ErrorCategory, P: Payload> From<Error<C, P>> for ErrorData {
     #[inline(always)]
-    fn from(error: Error<C>) -> Self {
-        error.0
+    fn from(error: Error<C, P>) -> Self {
+        error.data()
     }
 }
@@ -1,4 +1,4 @@
-use crate::ErrorCode;
+use crate::{ErrorCode, PayloadValue};
 
 use core::{
     fmt::{self, Debug, Formatter},
@@ -60,6 +60,14 @@ pub trait ErrorCategory: Copy + Into<ErrorCode> + From<ErrorCode> + Debug {
     /// The text name of this category used for formatting.
     const NAME: &'static str;
 
+    /// Whether this category has wide error codes.
+    ///
+    /// The error codes of a wide category can have a value from `0` to `255` instead of `0`
+    /// to `15`. Because a wide error code only fits at the front of an error chain (see
+    /// [`ErrorData`](crate::ErrorData)), a wide category must not be linked by any other
+    /// category. The derive macro checks this at compile time.
+    const WIDE: bool = false;
+
     /// Type of linked error category 0.
     ///
     /// Set to [`Unused`] if unused.
@@ -108,8 +116,41 @@ pub trait ErrorCategory: Copy + Into<ErrorCode> + From<ErrorCode> + Debug {
             format_chained::<Self::L5>,
         ]
     }
+
+    /// Debug format this error code with the payload `value` attached to it.
+    ///
+    /// This is used to format the most recent error code of an [`Error`](crate::Error)
+    /// with a [`Payload`](crate::Payload), and every chained error code with
+    /// [`PayloadValue::Missing`]. The derive macro implements this method using the
+    /// `{value}` placeholder in the `#[error(...)]` attribute, and prints the doc summary
+    /// or the variant name instead of a missing field. The default implementation ignores
+    /// `value` and delegates to [`Debug::fmt()`].
+    fn fmt_with_value(&self, value: PayloadValue, f: &mut Formatter<'_>) -> fmt::Result {
+        let _ = value;
+        Debug::fmt(self, f)
+    }
+}
+
+/// A conversion of the error codes of the [error category](ErrorCategory) `C` into error
+/// codes of this error category.
+///
+/// This allows an [`Error<C>`](crate::Error) to become an `Error<Self>` with the same
+/// error chain, by replacing only its most recent error code (see
+/// [`Error::map_category()`](crate::Error::map_category())). Because the error chain is
+/// kept, every category that `C` links to must be linked by this category at the same
+/// index.
+///
+/// The derive macro implements this trait using the `#[error(map(...))]` attribute, which
+/// also checks the links at compile time.
+pub trait FromCategory<C: ErrorCategory>: ErrorCategory {
+    /// Convert `error_code` of category `C` into an error code of this category.
+    fn from_category(error_code: C) -> Self;
 }
 
+/// The formatter index that marks the next error code in the error chain as an error code
+/// of an unlinked error category (see [`Unlinked`]).
+pub(crate) const UNLINKED_INDEX: u8 = 6;
+
 /// A handle to a type that implements [`ErrorCategory`].
 #[derive(Debug)]
 pub struct ErrorCategoryHandle {
@@ -158,7 +199,12 @@ impl Eq for ErrorCategoryHandle {}
 /// if `next_formatter` is `Some`.
 ///
 /// If `f` is `Some()` the following format is used:  
-///    `{C::NAME}({error_code}): {<error_code as C>:?}`
+///    `{C::NAME}({error_code}): {<error_code as C>:?}`  
+/// where the error code is formatted using [`ErrorCategory::fmt_with_value()`] with
+/// [`PayloadValue::Missing`].
+///
+/// A `next_formatter` of `6` always returns the formatter of [`Unlinked`], because it marks
+/// the next error code as one of an unlinked error category.
 pub fn format_chained<C: ErrorCategory>(
     error_code: ErrorCode,
     next_formatter: Option<u8>,
@@ -168,8 +214,10 @@ pub fn format_chained<C: ErrorCategory>(
     Result<Option<ErrorCodeFormatterVal>, fmt::Error>,
 ) {
     let fmt_res = if let Some(f) = f {
+        // The payload of a chained error code is not known.
         let err: C = error_code.into();
-        write!(f, "{}({}): {:?}", C::NAME, error_code, err)
+        write!(f, "{}({}): ", C::NAME, error_code)
+            .and_then(|_| err.fmt_with_value(PayloadValue::Missing, f))
     } else {
         Ok(())
     };
@@ -179,6 +227,9 @@ pub fn format_chained<C: ErrorCategory>(
         fmt_res.map(|_| {
             // Get the next formatter function if `next_formatter` is `Some`.
             next_formatter.and_then(|idx| {
+                if idx == UNLINKED_INDEX {
+                    return Some(ErrorCodeFormatterVal::new(format_chained::<Unlinked>));
+                }
                 let idx = idx as usize;
                 let formatters = C::chainable_category_formatters();
 
@@ -194,6 +245,9 @@ pub fn format_chained<C: ErrorCategory>(
 
 /// This marker type is used for any [`ErrorCategory::L0`] to [`ErrorCategory::L5`]
 /// which is unused.
+///
+/// It has no variants, so an error code is never converted to it in a valid error chain.
+/// Doing so anyway panics, or never returns if the feature `no-panic` is enabled.
 #[derive(Debug, Clone, Copy)]
 pub enum Unused {}
 
@@ -213,12 +267,59 @@ impl ErrorCategory for Unused {
 
 impl From<ErrorCode> for Unused {
     fn from(_: ErrorCode) -> Self {
-        unreachable!()
+        #[cfg(not(feature = "no-panic"))]
+        unreachable!("tried to convert an error code to `Unused`");
+        #[cfg(feature = "no-panic")]
+        loop {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+impl From<Unused> for ErrorCode {
+    fn from(val: Unused) -> Self {
+        match val {}
+    }
+}
+
+/// This error category stands in for an error code of an unlinked error category in the
+/// error chain.
+///
+/// It is recorded by [`DynError::chain_or_unlinked()`](crate::DynError::chain_or_unlinked())
+/// instead of panicking, when the chained error's category is not linked. Only the error
+/// code is kept, the error chain has no room to store which category it belonged to. For
+/// the same reason, every older error code in the chain is reported as `Unlinked` too.
+///
+/// An unlinked error code is formatted as `<unlinked>({error_code}): unknown error`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Unlinked(pub ErrorCode);
+
+impl ErrorCategory for Unlinked {
+    const NAME: &'static str = "<unlinked>";
+    // An unlinked error code can be followed by error codes of any category, which are
+    // unknown as well.
+    type L0 = Unlinked;
+    type L1 = Unlinked;
+    type L2 = Unlinked;
+    type L3 = Unlinked;
+    type L4 = Unlinked;
+    type L5 = Unlinked;
+}
+
+impl Debug for Unlinked {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown error")
+    }
+}
+
+impl From<ErrorCode> for Unlinked {
+    fn from(error_code: ErrorCode) -> Self {
+        Unlinked(error_code)
     }
 }
 
-impl Into<ErrorCode> for Unused {
-    fn into(self) -> ErrorCode {
-        match self {}
+impl From<Unlinked> for ErrorCode {
+    fn from(val: Unlinked) -> Self {
+        val.0
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "no-panic")]
+use crate::error_category::UNLINKED_INDEX;
 #[allow(unused_imports)]
 use crate::ErrorCategory;
 use crate::ErrorCode;
@@ -10,6 +12,9 @@ use crate::ErrorCode;
 /// [`ResultChainError::chain_err()`](super::ResultChainError::chain_err())) you can make
 /// before the chain overflows, and it either panics (if the feature `panic-on-overflow`
 /// is enabled) or the oldest error code gets lost.
+///
+/// If the most recent error code is a wide error code (see [`ErrorData`]), only
+/// `ERROR_CHAIN_LEN - 1` error codes can be chained.
 pub const ERROR_CHAIN_LEN: usize = 4;
 /// The entire data of the error and its error code chain.
 ///
@@ -26,7 +31,7 @@ pub const ERROR_CHAIN_LEN: usize = 4;
 ///   - `b16..b20`: chained error code 3
 /// - Bits `b20..b32` contain 4 formatter indices, each index has 3 bits.
 ///   - `b20..b23`: formatter `index + 1` of chained error 0 (`0` means not present)
-///                 (returned by [`first_formatter_index()`](Self::first_formatter_index()))
+///     (returned by [`first_formatter_index()`](Self::first_formatter_index()))
 ///   - `b23..b26`: formatter `index + 1` of chained error 1 (`0` means not present)
 ///   - `b26..b29`: formatter `index + 1` of chained error 2 (`0` means not present)
 ///   - `b29..b32`: formatter `index + 1` of chained error 3 (`0` means not present)
@@ -56,6 +61,21 @@ pub const ERROR_CHAIN_LEN: usize = 4;
 /// This is only possible if the [`ErrorCategory`] associated with the called formatter
 /// function is linked to the [`ErrorCategory`] of the next error code in the chain.
 ///
+/// ## Wide error codes
+/// The most recent error code can also be a wide error code (`16` to `255`) of a [wide
+/// error category](ErrorCategory::WIDE). In that case the high 4 bits of the error code
+/// are stored in the slot of chained error code 3 (`b16..b20`), while the formatter index
+/// of chained error 3 (`b29..b32`) stays `0`. A chained error code is only present if its
+/// formatter index is, so a non-zero code in that slot without a formatter index marks
+/// the current error code as wide. This means the error chain of a wide error can only
+/// have three entries. Because wide [error categories](ErrorCategory) can't be linked, a
+/// wide error code is never chained into the error chain itself.
+///
+/// ## Unlinked error codes
+/// The formatter index `6` (stored as `7`) marks the chained error code as an error code of
+/// an [unlinked](crate::Unlinked) error category. It can be stored at any position of the
+/// error chain.
+///
 /// An [error category](ErrorCategory) `A` is linked to an [error category](ErrorCategory)
 /// `B` if at least one of the [`A::L1`](ErrorCategory::L1) to
 /// [`A::L5`](ErrorCategory::L1) associated types is `B` and the `n`th element (where `n`
@@ -76,10 +96,13 @@ pub struct ErrorData {
     ///   - `b16..b20`: chained error code 3
     /// - Bits `b20..b32` contain 4 formatter indices, each index has 3 bits.
     ///   - `b20..b23`: formatter `index + 1` of chained error 0 (`0` means not present)
-    ///                 (returned by `Self::first_formatter_index()`)
+    ///     (returned by `Self::first_formatter_index()`)
     ///   - `b23..b26`: formatter `index + 1` of chained error 1 (`0` means not present)
     ///   - `b26..b29`: formatter `index + 1` of chained error 2 (`0` means not present)
     ///   - `b29..b32`: formatter `index + 1` of chained error 3 (`0` means not present)
+    ///
+    /// If `b29..b32` is `0` but `b16..b20` is not, the current error code is wide and
+    /// `b16..b20` contains its high 4 bits.
     data: u32,
 }
 
@@ -111,39 +134,127 @@ mod consts {
     pub const fn make_formatter_idx(value: u8) -> u32 {
         (value & 0b0111) as u32
     }
+
+    /// The formatter index of chained error 3, which is `0` if the current error code is
+    /// wide.
+    pub const WIDE_SLOT_FORMATTER_MASK: u32 = FORMATTER_MASK[3];
+    /// The code slot that contains the high 4 bits of a wide error code.
+    pub const WIDE_HIGH_MASK: u32 = CODE_MASK[4];
+    /// The bit offset of the high 4 bits of a wide error code.
+    pub const WIDE_HIGH_BITOFFSET: u32 = 16;
+
+    /// Make the high 4 bits for `value`, or `0` if `value` is not wide.
+    #[inline(always)]
+    pub const fn make_wide_ext(value: super::ErrorCode) -> u32 {
+        ((value >> CODE_WIDTH) as u32) << WIDE_HIGH_BITOFFSET
+    }
 }
 
 impl ErrorData {
     /// Create new `ErrorData` that contains the supplied `error_code` and has an empty chain.
     pub const fn new(error_code: ErrorCode) -> ErrorData {
         ErrorData {
-            data: error_code as u32 & consts::CODE_MASK[0],
+            data: consts::make_code(error_code) | consts::make_wide_ext(error_code),
         }
     }
 
     /// Replace the error code with `code` and return the old one.     
     ///
     /// Note: That the categories of the new error code and the old must be the same.
+    ///
+    /// If `code` is wide and the old error code is not, the high bits of `code` take the
+    /// place of the oldest chained error code, which is dropped if the chain is full.
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and a chained error code has to be
+    /// dropped, this function will panic.
     pub fn set_code(&mut self, code: ErrorCode) -> ErrorCode {
-        let old_ec = consts::make_code(self.data as u8) as ErrorCode;
-        self.data = (self.data & !(consts::CODE_MASK[0])) | consts::make_code(code);
+        let old_ec = self.code();
+        let wide_ext = consts::make_wide_ext(code);
+        if wide_ext != 0 && !self.is_wide() {
+            #[cfg_attr(
+                not(all(feature = "panic-on-overflow", not(feature = "no-panic"))),
+                allow(unused_variables)
+            )]
+            let overflow = self.drop_wide_slot();
+
+            #[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+            debug_assert!(
+                overflow.is_none(),
+                "setting a wide error code overflowed; error chain is full"
+            );
+        }
+
+        let mask = if self.is_wide() {
+            consts::CODE_MASK[0] | consts::WIDE_HIGH_MASK
+        } else {
+            consts::CODE_MASK[0]
+        };
+        self.data = (self.data & !mask) | consts::make_code(code) | wide_ext;
         old_ec
     }
 
     /// Get the most recent error code of the error.
     #[inline]
     pub fn code(&self) -> ErrorCode {
-        (self.data & consts::CODE_MASK[0]) as ErrorCode
+        let high = if self.is_wide() {
+            (self.data & consts::WIDE_HIGH_MASK)
+                >> (consts::WIDE_HIGH_BITOFFSET - consts::CODE_WIDTH)
+        } else {
+            0
+        };
+        ((self.data & consts::CODE_MASK[0]) | high) as ErrorCode
+    }
+
+    /// Whether the most recent error code is a wide error code (greater than `15`).
+    #[inline]
+    pub const fn is_wide(&self) -> bool {
+        (self.data & consts::WIDE_SLOT_FORMATTER_MASK) == 0
+            && (self.data & consts::WIDE_HIGH_MASK) != 0
+    }
+
+    /// Get the number of error codes that can be chained to this error.
+    ///
+    /// This is [`ERROR_CHAIN_LEN`] unless the most recent error code is wide.
+    #[inline]
+    pub const fn chain_capacity(&self) -> usize {
+        if self.is_wide() {
+            ERROR_CHAIN_LEN - 1
+        } else {
+            ERROR_CHAIN_LEN
+        }
+    }
+
+    /// Remove chained error 3 to make room for the high bits of a wide error code.
+    ///
+    /// Returns the removed error code and formatter index if the slot was used.
+    fn drop_wide_slot(&mut self) -> Option<(ErrorCode, u8)> {
+        let fmt_index_back = self.data & consts::WIDE_SLOT_FORMATTER_MASK;
+        let result = if fmt_index_back > 0 {
+            let ec_back = (self.data & consts::WIDE_HIGH_MASK) >> consts::WIDE_HIGH_BITOFFSET;
+            let fmt_index_back = fmt_index_back
+                >> ((ERROR_CHAIN_LEN as u32 - 1) * consts::FORMATTER_IDX_WIDTH
+                    + consts::FORMATTER_BITOFFSET);
+
+            Some((ec_back as ErrorCode, (fmt_index_back - 1) as u8))
+        } else {
+            None
+        };
+        self.data &= !(consts::WIDE_SLOT_FORMATTER_MASK | consts::WIDE_HIGH_MASK);
+        result
     }
 
     /// Get the first formatter index in the chain if available.
     pub fn first_formatter_index(&self) -> Option<u8> {
         let fmt_index =
             ((self.data & consts::FORMATTER_MASK[0]) >> consts::FORMATTER_BITOFFSET) as u8;
-        if fmt_index > 0 {
-            Some(fmt_index - 1)
-        } else {
-            None
+        fmt_index.checked_sub(1)
+    }
+
+    /// Turn a wide current error code into a narrow one by dropping its high 4 bits.
+    pub(crate) fn strip_wide(&mut self) {
+        if self.is_wide() {
+            self.set_code(self.code() & 0b1111);
         }
     }
 
@@ -151,20 +262,21 @@ impl ErrorData {
     pub fn chain_len(&self) -> usize {
         // If the formatter is zero that means it is not present.
         let mut mask = consts::FORMATTER_MASK[0];
+        let chain_capacity = self.chain_capacity();
 
-        for fmt_index in 0..ERROR_CHAIN_LEN {
+        for fmt_index in 0..chain_capacity {
             if (self.data & mask) == 0 {
                 return fmt_index;
             }
             mask <<= consts::FORMATTER_IDX_WIDTH;
         }
-        ERROR_CHAIN_LEN
+        chain_capacity
     }
 
     /// Whether the error chain is full.
     #[inline]
     pub fn chain_full(&self) -> bool {
-        self.chain_len() == ERROR_CHAIN_LEN
+        self.chain_len() == self.chain_capacity()
     }
 
     /// Prepend the current error code to the front of the error chain and set the current error
@@ -173,13 +285,19 @@ impl ErrorData {
     /// Returns the back of the error chain before modification if it gets overwritten by
     /// this operation (when the chain overflows).
     ///
-    /// Note: `error_code` is masked to the first 4 bits and `category_index` is masked to
-    /// the first 3 bits.
+    /// If `error_code` is wide, the oldest error in the chain is also lost if the chain
+    /// already contains three error codes.
+    ///
+    /// Note: `category_index` is masked to the first 3 bits. The current error code must
+    /// not be wide, because wide error codes can't be chained.
     pub fn push_front(
         &mut self,
         error_code: ErrorCode,
         category_index: u8,
     ) -> Option<(ErrorCode, u8)> {
+        #[cfg(not(feature = "no-panic"))]
+        debug_assert!(!self.is_wide(), "cannot chain a wide error code");
+
         // Get the last error code and formatter index in the chain,
         // if the formatter index is greater `0` that means the chain is full
         // and we return these from the function.
@@ -197,14 +315,21 @@ impl ErrorData {
         };
 
         let fmt_indices = ((self.data & consts::ALL_FORMATTER_MASK) << consts::FORMATTER_IDX_WIDTH)
-            | (consts::make_formatter_idx(category_index + 1) << consts::FORMATTER_BITOFFSET);
+            | (consts::make_formatter_idx(category_index.wrapping_add(1))
+                << consts::FORMATTER_BITOFFSET);
 
         let err_codes = ((self.data << consts::CODE_WIDTH) & consts::ALL_CODE_MASK)
             | consts::make_code(error_code);
 
         self.data = fmt_indices | err_codes;
 
-        result
+        if consts::make_wide_ext(error_code) != 0 {
+            let wide_result = self.drop_wide_slot();
+            self.data |= consts::make_wide_ext(error_code);
+            result.or(wide_result)
+        } else {
+            result
+        }
     }
 
     /// Chain this error with a new error specified by `error_code`.
@@ -225,20 +350,122 @@ impl ErrorData {
     /// the error chain is already full, the last error in the chain will be lost.
     pub fn chain(&mut self, error_code: ErrorCode, category_index: u8) {
         // Returns the last error in the chain if it's full.
+        #[cfg_attr(
+            not(all(feature = "panic-on-overflow", not(feature = "no-panic"))),
+            allow(unused_variables)
+        )]
         let overflow = self.push_front(error_code, category_index);
 
-        #[cfg(feature = "panic-on-overflow")]
+        #[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
         debug_assert!(
             overflow.is_none(),
             "chaining two errors overflowed; error chain is full"
         );
     }
 
+    /// Append the error `source` with its chain to the back of the error chain.
+    ///
+    /// - `source`: The error whose current error code is appended after the oldest error
+    ///   code of this error, followed by its error chain.
+    /// - `category_index`: The index of the
+    ///   [`ErrorCodeFormatter`](crate::ErrorCodeFormatter) of `source`'s current error code
+    ///   in the slice returned by
+    ///   [`T::chainable_category_formatters()`](ErrorCategory::chainable_category_formatters())
+    ///   where `T` is the [error category](ErrorCategory) that the oldest error code of
+    ///   this error belongs to.
+    ///
+    /// Returns `true` if error codes of `source` had to be dropped because they did not
+    /// fit into the error chain. The oldest error codes are dropped first.
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and error codes have to be dropped,
+    /// this function will panic. The current error code of `source` must not be wide,
+    /// because wide error codes can't be chained.
+    pub fn concat(&mut self, source: ErrorData, category_index: u8) -> bool {
+        #[cfg(not(feature = "no-panic"))]
+        debug_assert!(!source.is_wide(), "cannot chain a wide error code");
+
+        // Collect the error codes of this error from the most recent to the oldest,
+        // together with the formatter index of the next error code in the chain.
+        let mut entries = [(0, 0); ERROR_CHAIN_LEN + 1];
+        let mut len = 1;
+        let mut next_fmt_index = self.first_formatter_index();
+        entries[0].0 = self.code();
+        for (error_code, fmt_index) in self.iter_chain() {
+            entries[len - 1].1 = next_fmt_index.unwrap_or_default();
+            entries[len].0 = error_code;
+            next_fmt_index = fmt_index;
+            len += 1;
+        }
+        entries[len - 1].1 = category_index;
+
+        let mut data = source;
+        let mut truncated = false;
+        for &(error_code, fmt_index) in entries[..len].iter().rev() {
+            truncated |= data.push_front(error_code, fmt_index).is_some();
+        }
+        *self = data;
+
+        #[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+        debug_assert!(
+            !truncated,
+            "concatenating two errors overflowed; error chain is full"
+        );
+        truncated
+    }
+
+    /// Append the error `source` with its chain to the back of the error chain, recording
+    /// its current error code as [unlinked](crate::Unlinked).
+    ///
+    /// The high 4 bits of a wide error code of `source` are lost. Returns `true` if error
+    /// codes of `source` had to be dropped (see [`concat()`](Self::concat())).
+    #[cfg(feature = "no-panic")]
+    pub(crate) fn concat_unlinked(&mut self, mut source: ErrorData) -> bool {
+        source.strip_wide();
+        self.concat(source, UNLINKED_INDEX)
+    }
+
+    /// Remove the current error code and make the first error code of the chain the
+    /// current one.
+    ///
+    /// Returns the formatter index of the new current error code (see
+    /// [`first_formatter_index()`](Self::first_formatter_index())), or `None` if the error
+    /// chain is empty, in which case nothing is changed.
+    pub fn pop_front(&mut self) -> Option<u8> {
+        let fmt_index = self.first_formatter_index()?;
+        if self.is_wide() {
+            self.drop_wide_slot();
+        }
+
+        let fmt_indices = ((self.data & consts::ALL_FORMATTER_MASK) >> consts::FORMATTER_IDX_WIDTH)
+            & consts::ALL_FORMATTER_MASK;
+        let err_codes = (self.data & consts::ALL_CODE_MASK) >> consts::CODE_WIDTH;
+
+        self.data = fmt_indices | err_codes;
+        Some(fmt_index)
+    }
+
+    /// Shorten the error chain to at most `len` chained error codes, removing the oldest
+    /// error codes.
+    ///
+    /// If `len` is greater or equal to the current chain length, nothing is changed.
+    pub fn truncate_chain(&mut self, len: usize) {
+        for i in len..self.chain_capacity() {
+            self.data &= !(consts::CODE_MASK[i + 1] | consts::FORMATTER_MASK[i]);
+        }
+    }
+
     /// Iterate over the error chain.
     pub(crate) fn iter_chain(&self) -> ErrorDataChainIter {
+        let data = if self.is_wide() {
+            self.data & !consts::WIDE_HIGH_MASK
+        } else {
+            self.data
+        };
+
         ErrorDataChainIter {
-            error_codes: (self.data & consts::ALL_CODE_MASK) >> consts::CODE_WIDTH,
-            formatters: (self.data & consts::ALL_FORMATTER_MASK) >> consts::FORMATTER_BITOFFSET,
+            error_codes: (data & consts::ALL_CODE_MASK) >> consts::CODE_WIDTH,
+            formatters: (data & consts::ALL_FORMATTER_MASK) >> consts::FORMATTER_BITOFFSET,
         }
     }
 }
@@ -262,14 +489,8 @@ impl Iterator for ErrorDataChainIter {
             self.error_codes >>= consts::CODE_WIDTH;
             self.formatters >>= consts::FORMATTER_IDX_WIDTH;
 
-            let next_fmt_index = {
-                let next_fmt_index = consts::make_formatter_idx(self.formatters as u8);
-                if next_fmt_index > 0 {
-                    Some(next_fmt_index as u8 - 1)
-                } else {
-                    None
-                }
-            };
+            let next_fmt_index =
+                (consts::make_formatter_idx(self.formatters as u8) as u8).checked_sub(1);
 
             Some((ec as ErrorCode, next_fmt_index))
         } else {
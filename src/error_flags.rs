@@ -0,0 +1,448 @@
+use crate::{Error, ErrorCategory, ErrorCategoryHandle, ErrorCode};
+use core::{
+    fmt::{self, Debug, Formatter},
+    iter::{FromIterator, FusedIterator},
+    marker::PhantomData,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign},
+};
+
+/// A set of error codes belonging to the [error category](ErrorCategory) `C`.
+///
+/// Because every error code can only have a value from `0` to `15`, this set is backed
+/// by a single [`u16`] where bit `n` represents the error code `n`. This makes it a good
+/// fit for latched fault flags as they are commonly used in motor-control or power
+/// firmware.
+///
+/// A [wide error category](ErrorCategory::WIDE) can be used as well, but its error codes
+/// greater than `15` can't be contained: [`set()`](ErrorFlags::set()) returns `false` and
+/// leaves the set unchanged, [`contains()`](ErrorFlags::contains()) returns `false`, and
+/// they are skipped when creating a set from an error code or an [`Error`] chain.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum MotorFault {
+///     OverCurrent,
+///     OverTemperature,
+///     UnderVoltage,
+/// }
+///
+/// let mut faults = ErrorFlags::new();
+/// faults.set(MotorFault::OverCurrent);
+/// faults.set(MotorFault::UnderVoltage);
+///
+/// assert!(faults.contains(MotorFault::OverCurrent));
+/// assert!(!faults.contains(MotorFault::OverTemperature));
+/// assert_eq!(faults.len(), 2);
+///
+/// faults.clear(MotorFault::OverCurrent);
+/// assert_eq!(format!("{:?}", faults), "MotorFault{UnderVoltage}");
+/// ```
+///
+/// See [`AtomicErrorFlags`] for a variant that can be shared with interrupt handlers.
+#[repr(transparent)]
+pub struct ErrorFlags<C>(u16, PhantomData<C>);
+
+/// Get the bit that represents `error_code` in an [`ErrorFlags`] value, or `0` if
+/// `error_code` is greater than `15`.
+#[inline(always)]
+const fn flag(error_code: ErrorCode) -> u16 {
+    match 1u16.checked_shl(error_code as u32) {
+        Some(flag) => flag,
+        None => 0,
+    }
+}
+
+impl<C> ErrorFlags<C> {
+    /// Create a new empty set.
+    #[inline(always)]
+    pub const fn new() -> ErrorFlags<C> {
+        ErrorFlags(0, PhantomData)
+    }
+
+    /// Create a set from its raw bits, where bit `n` represents the error code `n`.
+    ///
+    /// Bits whose error code is not part of the [`ErrorCategory`] `C` are kept as they
+    /// are. They are counted by [`len()`](ErrorFlags::len()) and take part in all set
+    /// operations, while [`iter()`](ErrorFlags::iter()) and debug formatting convert them
+    /// using `C`'s [`From<ErrorCode>`] implementation. For a derived category that is its
+    /// `#[error(unknown)]` variant if it has one, otherwise it panics in debug builds
+    /// (unless the feature `no-panic` is enabled) and returns the last variant.
+    #[inline(always)]
+    pub const fn from_bits(bits: u16) -> ErrorFlags<C> {
+        ErrorFlags(bits, PhantomData)
+    }
+
+    /// Get the raw bits of this set, where bit `n` represents the error code `n`.
+    #[inline(always)]
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Whether no error code is contained in this set.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Get the number of error codes contained in this set.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Remove all error codes from this set.
+    #[inline(always)]
+    pub fn clear_all(&mut self) {
+        self.0 = 0;
+    }
+
+    /// Get the union of this set and `other`.
+    #[inline(always)]
+    pub const fn union(self, other: ErrorFlags<C>) -> ErrorFlags<C> {
+        ErrorFlags(self.0 | other.0, PhantomData)
+    }
+
+    /// Get the intersection of this set and `other`.
+    #[inline(always)]
+    pub const fn intersection(self, other: ErrorFlags<C>) -> ErrorFlags<C> {
+        ErrorFlags(self.0 & other.0, PhantomData)
+    }
+
+    /// Get all error codes of this set that are not contained in `other`.
+    #[inline(always)]
+    pub const fn difference(self, other: ErrorFlags<C>) -> ErrorFlags<C> {
+        ErrorFlags(self.0 & !other.0, PhantomData)
+    }
+
+    /// Get all error codes that are contained in either this set or `other`, but not in
+    /// both.
+    #[inline(always)]
+    pub const fn symmetric_difference(self, other: ErrorFlags<C>) -> ErrorFlags<C> {
+        ErrorFlags(self.0 ^ other.0, PhantomData)
+    }
+
+    /// Whether all error codes of this set are also contained in `other`.
+    #[inline(always)]
+    pub const fn is_subset(&self, other: &ErrorFlags<C>) -> bool {
+        self.0 & !other.0 == 0
+    }
+}
+
+impl<C: ErrorCategory> ErrorFlags<C> {
+    /// Add `error_code` to this set.
+    ///
+    /// Returns `true` if `error_code` was not already contained in this set. Always
+    /// returns `false` for error codes greater than `15`, which can't be contained.
+    #[inline]
+    pub fn set(&mut self, error_code: C) -> bool {
+        let flag = flag(error_code.into());
+        let newly_set = flag != 0 && self.0 & flag == 0;
+        self.0 |= flag;
+        newly_set
+    }
+
+    /// Remove `error_code` from this set.
+    ///
+    /// Returns `true` if `error_code` was contained in this set.
+    #[inline]
+    pub fn clear(&mut self, error_code: C) -> bool {
+        let flag = flag(error_code.into());
+        let was_set = self.0 & flag != 0;
+        self.0 &= !flag;
+        was_set
+    }
+
+    /// Whether `error_code` is contained in this set.
+    #[inline]
+    pub fn contains(&self, error_code: C) -> bool {
+        self.0 & flag(error_code.into()) != 0
+    }
+
+    /// Add every error code of [error category](ErrorCategory) `C` that is contained in
+    /// the chain of `error` to this set.
+    pub fn set_from_chain<T: ErrorCategory>(&mut self, error: &Error<T>) {
+        let category_handle = ErrorCategoryHandle::new::<C>();
+        for (ec, handle) in error.iter() {
+            if handle == category_handle {
+                self.0 |= flag(ec);
+            }
+        }
+    }
+
+    /// Create an iterator over all error codes contained in this set, from the lowest to
+    /// the highest error code.
+    #[inline]
+    pub fn iter(&self) -> ErrorFlagsIter<C> {
+        ErrorFlagsIter {
+            bits: self.0,
+            _category: PhantomData,
+        }
+    }
+}
+
+/// An iterator over all error codes contained in an [`ErrorFlags`] value.
+pub struct ErrorFlagsIter<C> {
+    bits: u16,
+    _category: PhantomData<C>,
+}
+
+impl<C: ErrorCategory> Iterator for ErrorFlagsIter<C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.bits == 0 {
+            None
+        } else {
+            let error_code = self.bits.trailing_zeros() as ErrorCode;
+            self.bits &= !flag(error_code);
+            Some(error_code.into())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+impl<C: ErrorCategory> ExactSizeIterator for ErrorFlagsIter<C> {}
+impl<C: ErrorCategory> FusedIterator for ErrorFlagsIter<C> {}
+
+impl<C: ErrorCategory> IntoIterator for ErrorFlags<C> {
+    type Item = C;
+    type IntoIter = ErrorFlagsIter<C>;
+
+    #[inline]
+    fn into_iter(self) -> ErrorFlagsIter<C> {
+        self.iter()
+    }
+}
+
+impl<C: ErrorCategory> Debug for ErrorFlags<C> {
+    /// Debug format all error codes contained in this set.
+    ///
+    /// Error message example:
+    /// ```txt
+    /// MotorFault{OverCurrent, UnderVoltage}
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(C::NAME)?;
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<C: ErrorCategory> PartialEq for ErrorFlags<C> {
+    fn eq(&self, other: &ErrorFlags<C>) -> bool {
+        self.0 == other.0
+    }
+}
+impl<C: ErrorCategory> Eq for ErrorFlags<C> {}
+
+impl<C: ErrorCategory> Clone for ErrorFlags<C> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: ErrorCategory> Copy for ErrorFlags<C> {}
+
+impl<C> Default for ErrorFlags<C> {
+    #[inline(always)]
+    fn default() -> Self {
+        ErrorFlags::new()
+    }
+}
+
+impl<C: ErrorCategory> From<C> for ErrorFlags<C> {
+    #[inline]
+    fn from(error_code: C) -> Self {
+        ErrorFlags(flag(error_code.into()), PhantomData)
+    }
+}
+
+impl<C: ErrorCategory, T: ErrorCategory> From<Error<T>> for ErrorFlags<C> {
+    /// Create a set that contains every error code of [error category](ErrorCategory) `C`
+    /// found in the chain of `error`.
+    fn from(error: Error<T>) -> Self {
+        let mut flags = ErrorFlags::new();
+        flags.set_from_chain(&error);
+        flags
+    }
+}
+
+impl<C: ErrorCategory> FromIterator<C> for ErrorFlags<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut flags = ErrorFlags::new();
+        flags.extend(iter);
+        flags
+    }
+}
+
+impl<C: ErrorCategory> Extend<C> for ErrorFlags<C> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for error_code in iter {
+            self.set(error_code);
+        }
+    }
+}
+
+macro_rules! impl_flags_ops {
+    ($([$op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, $func:ident]),*) => {
+        $(
+            impl<C> $op for ErrorFlags<C> {
+                type Output = ErrorFlags<C>;
+
+                #[inline(always)]
+                fn $op_fn(self, rhs: ErrorFlags<C>) -> ErrorFlags<C> {
+                    self.$func(rhs)
+                }
+            }
+
+            impl<C> $op_assign for ErrorFlags<C> {
+                #[inline(always)]
+                fn $op_assign_fn(&mut self, rhs: ErrorFlags<C>) {
+                    *self = ErrorFlags::from_bits(self.0).$func(rhs);
+                }
+            }
+        )+
+    };
+}
+
+impl_flags_ops!(
+    [BitOr, bitor, BitOrAssign, bitor_assign, union],
+    [BitAnd, bitand, BitAndAssign, bitand_assign, intersection],
+    [Sub, sub, SubAssign, sub_assign, difference],
+    [
+        BitXor,
+        bitxor,
+        BitXorAssign,
+        bitxor_assign,
+        symmetric_difference
+    ]
+);
+
+#[cfg(target_has_atomic = "16")]
+pub use self::atomic::AtomicErrorFlags;
+
+#[cfg(target_has_atomic = "16")]
+mod atomic {
+    use super::{flag, ErrorFlags};
+    use crate::ErrorCategory;
+    use core::{
+        fmt::{self, Debug, Formatter},
+        marker::PhantomData,
+        sync::atomic::{AtomicU16, Ordering},
+    };
+
+    /// An atomic [`ErrorFlags`] value that can be shared between threads or with
+    /// interrupt service routines.
+    ///
+    /// All read-modify-write operations use [`Ordering::AcqRel`], all loads use
+    /// [`Ordering::Acquire`] and all stores use [`Ordering::Release`].
+    ///
+    #[cfg_attr(feature = "derive", doc = "```")]
+    #[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+    /// # use embedded_error_chain::prelude::*;
+    /// # use embedded_error_chain::AtomicErrorFlags;
+    /// #[derive(Clone, Copy, ErrorCategory)]
+    /// #[repr(u8)]
+    /// enum MotorFault {
+    ///     OverCurrent,
+    ///     OverTemperature,
+    /// }
+    ///
+    /// static FAULTS: AtomicErrorFlags<MotorFault> = AtomicErrorFlags::new();
+    ///
+    /// // in the interrupt handler
+    /// FAULTS.set(MotorFault::OverCurrent);
+    ///
+    /// // in the main loop
+    /// let faults = FAULTS.take();
+    /// assert!(faults.contains(MotorFault::OverCurrent));
+    /// assert!(FAULTS.load().is_empty());
+    /// ```
+    #[repr(transparent)]
+    pub struct AtomicErrorFlags<C>(AtomicU16, PhantomData<fn() -> C>);
+
+    impl<C> AtomicErrorFlags<C> {
+        /// Create a new empty set.
+        #[inline(always)]
+        pub const fn new() -> AtomicErrorFlags<C> {
+            AtomicErrorFlags(AtomicU16::new(0), PhantomData)
+        }
+
+        /// Load the current value of this set.
+        #[inline]
+        pub fn load(&self) -> ErrorFlags<C> {
+            ErrorFlags::from_bits(self.0.load(Ordering::Acquire))
+        }
+
+        /// Replace the current value of this set with `flags`.
+        #[inline]
+        pub fn store(&self, flags: ErrorFlags<C>) {
+            self.0.store(flags.bits(), Ordering::Release)
+        }
+
+        /// Return the current value of this set and clear it.
+        #[inline]
+        pub fn take(&self) -> ErrorFlags<C> {
+            ErrorFlags::from_bits(self.0.swap(0, Ordering::AcqRel))
+        }
+
+        /// Add all error codes of `flags` to this set and return the previous value.
+        #[inline]
+        pub fn set_all(&self, flags: ErrorFlags<C>) -> ErrorFlags<C> {
+            ErrorFlags::from_bits(self.0.fetch_or(flags.bits(), Ordering::AcqRel))
+        }
+
+        /// Remove all error codes of `flags` from this set and return the previous value.
+        #[inline]
+        pub fn clear_all(&self, flags: ErrorFlags<C>) -> ErrorFlags<C> {
+            ErrorFlags::from_bits(self.0.fetch_and(!flags.bits(), Ordering::AcqRel))
+        }
+    }
+
+    impl<C: ErrorCategory> AtomicErrorFlags<C> {
+        /// Add `error_code` to this set.
+        ///
+        /// Returns `true` if `error_code` was not already contained in this set. Always
+        /// returns `false` for error codes greater than `15`, which can't be contained.
+        #[inline]
+        pub fn set(&self, error_code: C) -> bool {
+            let flag = flag(error_code.into());
+            flag != 0 && self.0.fetch_or(flag, Ordering::AcqRel) & flag == 0
+        }
+
+        /// Remove `error_code` from this set.
+        ///
+        /// Returns `true` if `error_code` was contained in this set.
+        #[inline]
+        pub fn clear(&self, error_code: C) -> bool {
+            let flag = flag(error_code.into());
+            self.0.fetch_and(!flag, Ordering::AcqRel) & flag != 0
+        }
+
+        /// Whether `error_code` is contained in this set.
+        #[inline]
+        pub fn contains(&self, error_code: C) -> bool {
+            self.load().contains(error_code)
+        }
+    }
+
+    impl<C> Default for AtomicErrorFlags<C> {
+        #[inline(always)]
+        fn default() -> Self {
+            AtomicErrorFlags::new()
+        }
+    }
+
+    impl<C: ErrorCategory> Debug for AtomicErrorFlags<C> {
+        /// Debug format all error codes currently contained in this set.
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            self.load().fmt(f)
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::DynError;
+use core::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+    slice,
+};
+
+/// A fixed-capacity collection of up to `N` independent errors.
+///
+/// Unlike an error chain, where every error is the cause of the next, the errors in an
+/// [`ErrorSet`] are unrelated to each other. This is useful for configuration validation
+/// or self-tests, where all failures should be reported instead of only the first one.
+///
+/// No allocation is needed: all errors are stored inline as [`DynError`] values. If an
+/// error is pushed while the set is full, the error is not stored and the number of such
+/// dropped errors is counted (see [`dropped()`](Self::dropped())).
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum ConfigError {
+///     InvalidBaudRate,
+///     InvalidAddress,
+/// }
+///
+/// fn check_baud_rate() -> Result<(), ConfigError> {
+///     Err(ConfigError::InvalidBaudRate)
+/// }
+///
+/// fn check_address() -> Result<(), ConfigError> {
+///     Err(ConfigError::InvalidAddress)
+/// }
+///
+/// fn validate() -> Result<(), ErrorSet<4>> {
+///     ErrorSet::new()
+///         .check(check_baud_rate)
+///         .check(check_address)
+///         .into_result()
+/// }
+///
+/// let errors = validate().unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ErrorSet<const N: usize> {
+    errors: [Option<DynError>; N],
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> ErrorSet<N> {
+    /// Create a new empty set.
+    pub const fn new() -> ErrorSet<N> {
+        ErrorSet {
+            errors: [None; N],
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Get the number of errors contained in this set.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this set contains no errors.
+    ///
+    /// Note that errors which were dropped because the set was full are not considered.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether this set is full.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Get the capacity of this set.
+    ///
+    /// Always returns `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get the number of errors that were dropped because this set was full.
+    #[inline]
+    pub const fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Add `error` to this set.
+    ///
+    /// If this set is already full, `error` is counted as dropped and returned in an
+    /// [`Err`].
+    pub fn push<E: Into<DynError>>(&mut self, error: E) -> Result<(), DynError> {
+        let error = error.into();
+        match self.errors.get_mut(self.len) {
+            Some(slot) => {
+                *slot = Some(error);
+                self.len += 1;
+                Ok(())
+            }
+            None => {
+                self.dropped = self.dropped.saturating_add(1);
+                Err(error)
+            }
+        }
+    }
+
+    /// Add the errors of all [`Err`] values yielded by `results` to this set, ignoring
+    /// all [`Ok`] values.
+    ///
+    /// Errors that don't fit are counted as dropped.
+    pub fn extend_from_results<T, E, I>(&mut self, results: I)
+    where
+        E: Into<DynError>,
+        I: IntoIterator<Item = Result<T, E>>,
+    {
+        for result in results {
+            if let Err(error) = result {
+                let _ = self.push(error);
+            }
+        }
+    }
+
+    /// Run `check` and add its error to this set if it fails.
+    ///
+    /// This can be used to run several independent checks and collect all their errors.
+    /// Errors that don't fit are counted as dropped.
+    pub fn check<T, E, F>(mut self, check: F) -> Self
+    where
+        E: Into<DynError>,
+        F: FnOnce() -> Result<T, E>,
+    {
+        if let Err(error) = check() {
+            let _ = self.push(error);
+        }
+        self
+    }
+
+    /// Return [`Ok`] if this set is empty, otherwise return [`Err`] containing this set.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() && self.dropped == 0 {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Create an iterator that iterates over all errors in this set in the order they
+    /// were added.
+    #[inline]
+    pub fn iter(&self) -> ErrorSetIter<'_> {
+        ErrorSetIter {
+            errors: self.errors.get(..self.len).unwrap_or_default().iter(),
+        }
+    }
+
+    /// Remove all errors from this set and reset the dropped count.
+    pub fn clear(&mut self) {
+        *self = ErrorSet::new();
+    }
+}
+
+/// An iterator over all errors in an [`ErrorSet`].
+pub struct ErrorSetIter<'a> {
+    errors: slice::Iter<'a, Option<DynError>>,
+}
+
+impl<'a> Iterator for ErrorSetIter<'a> {
+    type Item = &'a DynError;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a DynError> {
+        self.errors.next().and_then(Option::as_ref)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.errors.size_hint()
+    }
+}
+impl<'a> ExactSizeIterator for ErrorSetIter<'a> {}
+impl<'a> FusedIterator for ErrorSetIter<'a> {}
+
+impl<'a, const N: usize> IntoIterator for &'a ErrorSet<N> {
+    type Item = &'a DynError;
+    type IntoIter = ErrorSetIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> ErrorSetIter<'a> {
+        self.iter()
+    }
+}
+
+impl<const N: usize> Default for ErrorSet<N> {
+    fn default() -> Self {
+        ErrorSet::new()
+    }
+}
+
+impl<const N: usize> PartialEq for ErrorSet<N> {
+    fn eq(&self, other: &ErrorSet<N>) -> bool {
+        self.dropped == other.dropped && self.iter().eq(other.iter())
+    }
+}
+impl<const N: usize> Eq for ErrorSet<N> {}
+
+impl<const N: usize> Debug for ErrorSet<N> {
+    /// Debug format all errors in this set, each with its chain.
+    ///
+    /// The chain of every error is printed as a separate block. Error message example:
+    /// ```txt
+    /// ConfigError(0): invalid baud rate
+    /// - UartError(1): unsupported clock
+    ///
+    /// ConfigError(1): invalid address
+    ///
+    /// (1 more error dropped)
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n\n")?;
+            }
+            error.fmt(f)?;
+        }
+
+        if self.dropped > 0 {
+            if !self.is_empty() {
+                f.write_str("\n\n")?;
+            }
+            write!(
+                f,
+                "({} more error{} dropped)",
+                self.dropped,
+                if self.dropped == 1 { "" } else { "s" }
+            )?;
+        }
+        Ok(())
+    }
+}
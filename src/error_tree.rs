@@ -0,0 +1,366 @@
+use crate::{DynError, Error, ErrorCategory};
+use core::{
+    fmt::{self, Debug, Formatter, Write},
+    iter::FusedIterator,
+    slice,
+};
+
+/// A single node of an [`ErrorTree`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Node {
+    error: DynError,
+    /// The index of the parent node, `None` if this is a top-level node.
+    parent: Option<usize>,
+}
+
+/// A fixed-capacity tree of up to `N` errors, where every error can have multiple
+/// causes.
+///
+/// An [`Error`] or [`DynError`] models the causes of an error as a singly linked list, so
+/// every error can only have a single cause. But sometimes an error is the result of
+/// multiple independent failures, for example sensor fusion failing because both the
+/// accelerometer and the gyroscope failed. An [`ErrorTree`] represents such errors as a
+/// tree of [`DynError`] nodes, where each node can still have its own linear chain.
+///
+/// All nodes are stored inline in an arena of `N` nodes, so no allocation is needed. The
+/// nodes are always stored in depth-first order. If a tree operation needs more than `N`
+/// nodes, and the feature `panic-on-overflow` is enabled, the operation will panic,
+/// otherwise the last nodes in depth-first order will be lost.
+///
+/// Multiple causes are combined using [`join()`] or [`ErrorTree::join()`], which results
+/// in a tree with multiple top-level nodes. Chaining such a tree with
+/// [`ErrorTree::chain()`] adds a new root node with all previous top-level nodes as its
+/// children.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// # use embedded_error_chain::{join, ErrorTree};
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum AccError {
+///     ReadoutFailed,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum GyroError {
+///     Timeout,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum FusionError {
+///     Failed,
+/// }
+///
+/// fn fuse() -> Result<(), ErrorTree<4>> {
+///     Err(join(AccError::ReadoutFailed, GyroError::Timeout).chain(FusionError::Failed))
+/// }
+///
+/// let err = fuse().unwrap_err();
+/// assert!(err.caused_by(AccError::ReadoutFailed));
+/// assert!(err.caused_by(GyroError::Timeout));
+/// assert_eq!(
+///     format!("{:?}", err),
+///     "FusionError(0): Failed\n  + AccError(0): ReadoutFailed\n  + GyroError(0): Timeout"
+/// );
+/// ```
+///
+/// Note that the [error categories](ErrorCategory) of the nodes don't have to be linked,
+/// because every node stores the formatter of its most recent error code.
+#[derive(Clone, Copy)]
+pub struct ErrorTree<const N: usize> {
+    nodes: [Option<Node>; N],
+    len: usize,
+}
+
+/// Combine the errors `a` and `b` into one [`ErrorTree`] with multiple top-level nodes.
+///
+/// The result is typically chained with an error code of the failed operation (see
+/// [`ErrorTree::chain()`]).
+pub fn join<A, B, const N: usize>(a: A, b: B) -> ErrorTree<N>
+where
+    A: Into<ErrorTree<N>>,
+    B: Into<ErrorTree<N>>,
+{
+    a.into().join(b)
+}
+
+impl<const N: usize> ErrorTree<N> {
+    /// Create a new empty tree.
+    pub const fn new() -> ErrorTree<N> {
+        ErrorTree {
+            nodes: [None; N],
+            len: 0,
+        }
+    }
+
+    /// Get the number of nodes in this tree.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this tree contains no nodes.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the capacity of this tree.
+    ///
+    /// Always returns `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get the error of the root node.
+    ///
+    /// Returns `None` if this tree is empty or has more than one top-level node.
+    pub fn root(&self) -> Option<&DynError> {
+        let mut top_level = self.nodes().filter(|node| node.parent.is_none());
+        match (top_level.next(), top_level.next()) {
+            (Some(node), None) => Some(&node.error),
+            _ => None,
+        }
+    }
+
+    /// Add all nodes of `other` as new top-level nodes to this tree.
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and the combined tree has more than
+    /// `N` nodes, this function will panic. If the feature is not enabled, the nodes of
+    /// `other` that don't fit are lost.
+    pub fn join<T: Into<ErrorTree<N>>>(mut self, other: T) -> Self {
+        let other = other.into();
+        let offset = self.len;
+
+        #[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+        debug_assert!(
+            self.len + other.len <= N,
+            "joining two error trees overflowed; error tree is full"
+        );
+
+        for node in other.nodes() {
+            let slot = match self.nodes.get_mut(self.len) {
+                Some(slot) => slot,
+                None => break,
+            };
+            *slot = Some(Node {
+                error: node.error,
+                parent: node.parent.map(|parent| parent.saturating_add(offset)),
+            });
+            self.len = self.len.saturating_add(1);
+        }
+        self
+    }
+
+    /// Add a new root node with the supplied `error_code` to this tree, and make all
+    /// previous top-level nodes its children.
+    ///
+    /// ### Panics
+    /// If the feature `panic-on-overflow` is enabled and this tree is already full
+    /// before this operation, this function will panic. If the feature is not enabled
+    /// and the tree is already full, the last node in depth-first order will be lost.
+    pub fn chain<C: ErrorCategory>(mut self, error_code: C) -> Self {
+        #[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+        debug_assert!(
+            self.len < N,
+            "chaining an error tree overflowed; error tree is full"
+        );
+
+        if N == 0 {
+            return self;
+        }
+        let len = self.len.min(N - 1);
+
+        for i in (0..len).rev() {
+            self.nodes[i + 1] = self.nodes[i].map(|node| Node {
+                error: node.error,
+                parent: Some(node.parent.map_or(0, |parent| parent.saturating_add(1))),
+            });
+        }
+        self.nodes[0] = Some(Node {
+            error: DynError::new(error_code),
+            parent: None,
+        });
+        self.len = len + 1;
+        self
+    }
+
+    /// Query if any node of this tree was caused by `error_code` which belongs to the
+    /// [error category](ErrorCategory) `T`.
+    pub fn caused_by<T: ErrorCategory>(&self, error_code: T) -> bool {
+        self.iter().any(|error| error.caused_by(error_code))
+    }
+
+    /// Query the first error code contained in this tree (in depth-first order) that
+    /// belongs to the [error category](ErrorCategory) `T`. Return `None` if this tree
+    /// does not contain an error code of the specified error category.
+    pub fn code_of_category<T: ErrorCategory>(&self) -> Option<T> {
+        self.iter().find_map(DynError::code_of_category)
+    }
+
+    /// Create an iterator that iterates over the errors of all nodes in this tree in
+    /// depth-first order.
+    #[inline]
+    pub fn iter(&self) -> ErrorTreeIter<'_> {
+        ErrorTreeIter {
+            nodes: self.nodes.get(..self.len).unwrap_or_default().iter(),
+        }
+    }
+
+    /// Create an iterator over all children of the node at `index` (in depth-first
+    /// order).
+    pub fn children(&self, index: usize) -> impl Iterator<Item = &DynError> + '_ {
+        self.nodes()
+            .filter(move |node| node.parent == Some(index))
+            .map(|node| &node.error)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &Node> + '_ {
+        self.nodes
+            .get(..self.len)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(Option::as_ref)
+    }
+
+    /// Get the depth of the node at `index`, top-level nodes have a depth of `0`.
+    fn depth(&self, mut index: usize) -> usize {
+        let mut depth: usize = 0;
+        while let Some(Some(Node {
+            parent: Some(parent),
+            ..
+        })) = self.nodes.get(index)
+        {
+            let parent = *parent;
+            index = parent;
+            depth = depth.saturating_add(1);
+        }
+        depth
+    }
+}
+
+/// An iterator over the errors of all nodes in an [`ErrorTree`].
+pub struct ErrorTreeIter<'a> {
+    nodes: slice::Iter<'a, Option<Node>>,
+}
+
+impl<'a> Iterator for ErrorTreeIter<'a> {
+    type Item = &'a DynError;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a DynError> {
+        self.nodes
+            .next()
+            .and_then(Option::as_ref)
+            .map(|node| &node.error)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+impl<'a> ExactSizeIterator for ErrorTreeIter<'a> {}
+impl<'a> FusedIterator for ErrorTreeIter<'a> {}
+
+impl<const N: usize> Default for ErrorTree<N> {
+    fn default() -> Self {
+        ErrorTree::new()
+    }
+}
+
+impl<const N: usize> PartialEq for ErrorTree<N> {
+    fn eq(&self, other: &ErrorTree<N>) -> bool {
+        self.nodes().eq(other.nodes())
+    }
+}
+impl<const N: usize> Eq for ErrorTree<N> {}
+
+impl<const N: usize> From<DynError> for ErrorTree<N> {
+    fn from(error: DynError) -> Self {
+        let mut tree = ErrorTree::new();
+        if N > 0 {
+            tree.nodes[0] = Some(Node {
+                error,
+                parent: None,
+            });
+            tree.len = 1;
+        }
+        tree
+    }
+}
+
+impl<C: ErrorCategory, const N: usize> From<Error<C>> for ErrorTree<N> {
+    #[inline]
+    fn from(error: Error<C>) -> Self {
+        DynError::from(error).into()
+    }
+}
+
+impl<C: ErrorCategory, const N: usize> From<C> for ErrorTree<N> {
+    #[inline]
+    fn from(error: C) -> Self {
+        DynError::new(error).into()
+    }
+}
+
+/// A [`fmt::Write`] adapter that indents every new line.
+struct Indented<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    indent: usize,
+}
+
+impl<'a, 'b> Write for Indented<'a, 'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some((line, next)) = crate::utils::split_line(rest) {
+            self.f.write_str(line)?;
+            self.f.write_char('\n')?;
+            for _ in 0..self.indent {
+                self.f.write_char(' ')?;
+            }
+            rest = next;
+        }
+        self.f.write_str(rest)
+    }
+}
+
+impl<const N: usize> Debug for ErrorTree<N> {
+    /// Debug format this tree as an indented tree.
+    ///
+    /// Every node is printed with its chain like a [`DynError`]. Child nodes are
+    /// prefixed with `+ ` and indented by two spaces per level. Error message example:
+    /// ```txt
+    /// FusionError(0): sensor fusion failed
+    ///   + AccError(0): readout failed
+    ///     - SpiError(0): bus error
+    ///   + GyroError(1): timeout
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, node) in (0..N).zip(self.nodes()) {
+            let depth = self.depth(i);
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            if depth > 0 {
+                for _ in 0..depth {
+                    f.write_str("  ")?;
+                }
+                f.write_str("+ ")?;
+            }
+
+            let mut indented = Indented {
+                f,
+                indent: depth
+                    .saturating_mul(2)
+                    .saturating_add(if depth > 0 { 2 } else { 0 }),
+            };
+            write!(indented, "{:?}", node.error)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{ChainError, Error, ErrorCategory};
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A trait that allows chaining if the [`Result`] of a [`Future`] contains an error
+/// value.
+///
+/// This is the asynchronous counterpart of [`ResultChainError`](crate::ResultChainError)
+/// and works for the same error types: it is implemented for all futures that output a
+/// [`Result`] whose error is an [`Error`], a [`DynError`](crate::DynError) or a bare
+/// [error category](ErrorCategory).
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// use embedded_error_chain::FutureChainError;
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum GyroAccError {
+///     ReadoutFailed,
+/// }
+///
+/// async fn spi_read() -> Result<u8, SpiError> {
+///     Err(SpiError::BusError)
+/// }
+///
+/// async fn gyro_acc_readout() -> Result<u8, Error<GyroAccError>> {
+///     spi_read().chain_err(GyroAccError::ReadoutFailed).await
+/// }
+/// ```
+pub trait FutureChainError<T, O: ErrorCategory, Tag>: Sized {
+    /// Wrap this future so that, once it completes with an [`Err`] value, the error is
+    /// chained with the supplied `error_code`, and an [`Ok`] value is forwarded
+    /// unchanged.
+    ///
+    /// ### Panics
+    /// The returned future panics when the wrapped future completes with an [`Err`] value
+    /// and the [error category](ErrorCategory) `O` is not linked with the
+    /// [`ErrorCategory`] of the most recent error code in the error.
+    fn chain_err(self, error_code: O) -> ChainErrFuture<Self, O, Tag>;
+}
+
+impl<F, OK, ERR, O, TAG> FutureChainError<OK, O, TAG> for F
+where
+    F: Future<Output = Result<OK, ERR>>,
+    O: ErrorCategory,
+    ERR: ChainError<O, TAG>,
+{
+    #[inline]
+    fn chain_err(self, error_code: O) -> ChainErrFuture<F, O, TAG> {
+        ChainErrFuture {
+            future: self,
+            error_code,
+            _tag: PhantomData,
+        }
+    }
+}
+
+/// The future returned by [`FutureChainError::chain_err()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ChainErrFuture<F, O, Tag> {
+    future: F,
+    error_code: O,
+    _tag: PhantomData<fn() -> Tag>,
+}
+
+impl<F, OK, ERR, O, TAG> Future for ChainErrFuture<F, O, TAG>
+where
+    F: Future<Output = Result<OK, ERR>>,
+    O: ErrorCategory,
+    ERR: ChainError<O, TAG>,
+{
+    type Output = Result<OK, Error<O>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let error_code = self.error_code;
+        // SAFETY: `future` is structurally pinned: it is never moved out of `self`, and
+        // `ChainErrFuture` implements neither `Drop` nor `Unpin` manually.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        match future.poll(cx) {
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err.chain(error_code))),
+            Poll::Ready(Ok(val)) => Poll::Ready(Ok(val)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
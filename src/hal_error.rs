@@ -0,0 +1,150 @@
+use crate::{
+    categories::{DigitalError, I2cError, SpiError},
+    marker, ChainError, Error, ErrorCategory,
+};
+use embedded_hal::{digital, i2c, spi};
+
+/// An error of an [embedded-hal](https://crates.io/crates/embedded-hal) driver that can be
+/// converted into an [error category](ErrorCategory) using its `kind()`.
+///
+/// This trait is implemented for all types that implement `spi::Error`
+/// ([`SpiError`]), `i2c::Error` ([`I2cError`]) or `digital::Error` ([`DigitalError`]).
+/// The `Tag` type parameter disambiguates between these implementations.
+pub trait HalError<Tag> {
+    /// The error category that the error kinds are converted into.
+    type Category: ErrorCategory;
+
+    /// Convert the kind of this error into [`Self::Category`](HalError::Category).
+    fn category(&self) -> Self::Category;
+}
+
+impl<E: spi::Error> HalError<marker::Spi_t> for E {
+    type Category = SpiError;
+
+    #[inline]
+    fn category(&self) -> SpiError {
+        self.kind().into()
+    }
+}
+
+impl<E: i2c::Error> HalError<marker::I2c_t> for E {
+    type Category = I2cError;
+
+    #[inline]
+    fn category(&self) -> I2cError {
+        self.kind().into()
+    }
+}
+
+impl<E: digital::Error> HalError<marker::Digital_t> for E {
+    type Category = DigitalError;
+
+    #[inline]
+    fn category(&self) -> DigitalError {
+        self.kind().into()
+    }
+}
+
+/// A trait that converts the embedded-hal error of a [`Result`] into an [`Error`].
+pub trait ResultKind<T, Tag> {
+    /// The error category that the error kinds are converted into.
+    type Category: ErrorCategory;
+
+    /// If the result contains an [`Err`] value, convert its kind into
+    /// [`Self::Category`](ResultKind::Category) and return [`Err`] with the resulting
+    /// [`Error`], otherwise forward the [`Ok`] value.
+    fn kind_err(self) -> Result<T, Error<Self::Category>>;
+}
+
+impl<T, E: HalError<Tag>, Tag> ResultKind<T, Tag> for Result<T, E> {
+    type Category = E::Category;
+
+    #[inline]
+    fn kind_err(self) -> Result<T, Error<E::Category>> {
+        self.map_err(|err| Error::new(err.category()))
+    }
+}
+
+/// A trait that converts the embedded-hal error of a [`Result`] into an [`Error`] and
+/// chains it in one call.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// use embedded_error_chain::{categories::SpiError, ResultChainKind};
+/// use embedded_hal::spi::ErrorKind;
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum SensorError {
+///     Bus,
+/// }
+///
+/// fn spi_transfer() -> Result<u8, ErrorKind> {
+///     Err(ErrorKind::Overrun)
+/// }
+///
+/// fn read_sensor() -> Result<u8, Error<SensorError>> {
+///     spi_transfer().chain_kind(SensorError::Bus)
+/// }
+///
+/// assert!(read_sensor().unwrap_err().caused_by(SpiError::Overrun));
+/// ```
+pub trait ResultChainKind<T, O: ErrorCategory, Tag> {
+    /// If the result contains an [`Err`] value, convert its kind into the
+    /// [category](HalError::Category) of the error, chain it with the supplied `error_code`
+    /// and return [`Err`] with the result, otherwise forward the [`Ok`] value.
+    ///
+    /// ### Panics
+    /// Panics under the same conditions as [`ChainError::chain()`].
+    fn chain_kind(self, error_code: O) -> Result<T, Error<O>>;
+}
+
+impl<T, E, O, KindTag, ChainTag> ResultChainKind<T, O, (KindTag, ChainTag)> for Result<T, E>
+where
+    E: HalError<KindTag>,
+    E::Category: ChainError<O, ChainTag>,
+    O: ErrorCategory,
+{
+    #[inline]
+    fn chain_kind(self, error_code: O) -> Result<T, Error<O>> {
+        self.map_err(|err| err.category().chain(error_code))
+    }
+}
+
+impl From<spi::ErrorKind> for SpiError {
+    fn from(kind: spi::ErrorKind) -> SpiError {
+        match kind {
+            spi::ErrorKind::Overrun => SpiError::Overrun,
+            spi::ErrorKind::ModeFault => SpiError::ModeFault,
+            spi::ErrorKind::FrameFormat => SpiError::FrameFormat,
+            spi::ErrorKind::ChipSelectFault => SpiError::ChipSelectFault,
+            _ => SpiError::Other,
+        }
+    }
+}
+
+impl From<i2c::ErrorKind> for I2cError {
+    fn from(kind: i2c::ErrorKind) -> I2cError {
+        match kind {
+            i2c::ErrorKind::Bus => I2cError::Bus,
+            i2c::ErrorKind::ArbitrationLoss => I2cError::ArbitrationLoss,
+            i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address) => {
+                I2cError::NoAcknowledgeAddress
+            }
+            i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Data) => {
+                I2cError::NoAcknowledgeData
+            }
+            i2c::ErrorKind::NoAcknowledge(_) => I2cError::NoAcknowledge,
+            i2c::ErrorKind::Overrun => I2cError::Overrun,
+            _ => I2cError::Other,
+        }
+    }
+}
+
+impl From<digital::ErrorKind> for DigitalError {
+    fn from(_: digital::ErrorKind) -> DigitalError {
+        DigitalError::Other
+    }
+}
@@ -13,15 +13,51 @@ does not have a type parameter for the current error code. Its size is a [`u32`]
 pointer ([`usize`]), which can be used to forward source errors of different categories
 to the caller.
 
+The [`categories`] module contains ready-made error categories for common embedded errors
+(SPI, I2C, serial, digital I/O, `core::fmt` and `core::num`), so that independent
+driver crates can link against shared categories.
+
 This library was inspired by libraries such as
 [error-chain](https://crates.io/crates/error-chain),
 [anyhow](https://crates.io/crates/anyhow) and
 [thiserror](https://crates.io/crates/thiserror), though it was made to work in `no_std`
 **and** no `liballoc` environments with very little memory overhead.
 
+## Cargo features
+- `panic-on-overflow` (enabled by default): Panic in debug builds if an error chain
+  overflows. If disabled, the oldest error code in the chain is lost instead.
+- `no-panic`: Remove every panic path from the library, overriding `panic-on-overflow`.
+  Chaining a `DynError` with an unlinked category using `ChainError::chain()` then
+  records an [`Unlinked`] entry, which loses the category names of the chained errors,
+  instead of panicking. Since features are additive, this changes the behaviour for all
+  crates in the dependency graph, so use `DynError::try_chain()` to handle unlinked
+  categories explicitly. Error categories without variants fail to compile, because
+  converting an error code into them would have to panic.
+- `derive` (enabled by default): The [`ErrorCategory`](derive@ErrorCategory) derive
+  macro. If disabled, the crate has no proc-macro dependencies and categories are
+  declared using the [`error_category!`] macro instead.
+- `location`: Record the source location of every entry in a `LocatedError` chain and
+  print it when debug formatting. If disabled, `LocatedError` is just an `Error` and no
+  locations are captured.
+- `embedded-hal`: Convert the errors of [embedded-hal](https://crates.io/crates/embedded-hal)
+  1.0 drivers into the SPI, I2C and digital I/O [`categories`] using their `kind()` (see
+  [`ResultChainKind`]).
+- `nb`: Chain the errors of non-blocking [nb](https://crates.io/crates/nb) results
+  without touching `WouldBlock` (see [`NbResultChainError`] and [`block_err!`]).
+- `std`: Use `std` instead of `core` in the code generated by the derive macro.
+- `nightly`: Use nightly-only const features for compile-time checks.
+
+## Minimum supported Rust version
+Rust 1.60 or later is required. [`AtomicErrorFlags`] is only available on targets with
+16-bit atomics, which is detected using `cfg(target_has_atomic)` (stable since 1.60), and
+the optional `embedded-hal` 1.0 and `nb` 1.x dependencies require 1.60 as well.
+
 ## Example
-```rust
-use embedded_error_chain::prelude::*;
+*/
+#![cfg_attr(feature = "derive", doc = "```")]
+#![cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/*!
+use embedded_error_chain::{match_error, prelude::*};
 
 #[derive(Clone, Copy, ErrorCategory)]
 #[repr(u8)]
@@ -55,15 +91,11 @@ fn main() {
 
     let readout = match gyro_acc_readout() {
         Ok(val) => val,
-        Err(err) => {
-            if let Some(spi_error) = err.code_of_category::<SpiError>() {
-                // try to fix it
-                0
-            }
-            else {
-                panic!("unfixable spi error");
-            }
-        }
+        Err(err) => match_error!(err, {
+            // try to fix it
+            SpiError::_ => 0,
+            _ => panic!("unfixable spi error"),
+        }),
     };
 }
 
@@ -91,31 +123,62 @@ fn calibrate() -> Result<(), DynError> {
 #![no_std]
 #![cfg_attr(feature = "nightly", feature(const_panic, const_fn))]
 #![warn(missing_docs)]
-#![allow(clippy::clippy::trivially_copy_pass_by_ref)]
+#![allow(clippy::trivially_copy_pass_by_ref)]
 
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod categories;
+mod context_error;
 mod dyn_error;
 mod error;
 mod error_category;
 mod error_data;
+mod error_flags;
+mod error_set;
+mod error_tree;
+mod future_error;
+#[cfg(feature = "embedded-hal")]
+mod hal_error;
+mod located_error;
+mod macros;
+#[cfg(feature = "nb")]
+mod nb_error;
+mod payload;
 
 #[doc(hidden)]
 pub mod utils;
 
+pub use context_error::{ContextError, ResultContext};
 pub use dyn_error::DynError;
-pub use error::{ChainError, Error, ErrorIter, ResultChainError};
+pub use error::{
+    ChainEntries, ChainEntry, ChainError, Error, ErrorIter, OptionChainError, ResultChainError,
+    ResultErrorExt,
+};
 pub use error_category::{
     format_chained, ErrorCategory, ErrorCategoryHandle, ErrorCodeFormatter, ErrorCodeFormatterVal,
+    FromCategory, Unlinked,
 };
 pub use error_data::{ErrorData, ERROR_CHAIN_LEN};
+#[cfg(target_has_atomic = "16")]
+pub use error_flags::AtomicErrorFlags;
+pub use error_flags::{ErrorFlags, ErrorFlagsIter};
+pub use error_set::{ErrorSet, ErrorSetIter};
+pub use error_tree::{join, ErrorTree, ErrorTreeIter};
+pub use future_error::{ChainErrFuture, FutureChainError};
+#[cfg(feature = "embedded-hal")]
+pub use hal_error::{HalError, ResultChainKind, ResultKind};
+pub use located_error::LocatedError;
+#[cfg(feature = "nb")]
+pub use nb_error::NbResultChainError;
+pub use payload::{Payload, PayloadCategory, PayloadValue};
 
 /// Everything for easy error handling.
 pub mod prelude {
     #[doc(no_inline)]
     pub use crate::{
-        ChainError, DynError, Error, ErrorCategory, ErrorCategoryHandle, ResultChainError,
+        ChainError, ContextError, DynError, Error, ErrorCategory, ErrorCategoryHandle, ErrorFlags,
+        ErrorSet, ErrorTree, OptionChainError, ResultChainError, ResultContext,
     };
 }
 
@@ -144,16 +207,41 @@ pub mod marker {
     #[allow(non_camel_case_types)]
     pub struct Concrete_t;
 
+    /// A tag type to disambiguate between `ChainError` trait implementations for
+    /// `LocatedError<T>` and for `Error<T>` or just `T`.
+    #[allow(non_camel_case_types)]
+    pub struct Located_t;
+
+    /// A tag type to disambiguate between `HalError` trait implementations for
+    /// `spi::Error`, `i2c::Error` and `digital::Error`.
+    #[cfg(feature = "embedded-hal")]
+    #[allow(non_camel_case_types)]
+    pub struct Spi_t;
+    /// A tag type to disambiguate between `HalError` trait implementations for
+    /// `spi::Error`, `i2c::Error` and `digital::Error`.
+    #[cfg(feature = "embedded-hal")]
+    #[allow(non_camel_case_types)]
+    pub struct I2c_t;
+    /// A tag type to disambiguate between `HalError` trait implementations for
+    /// `spi::Error`, `i2c::Error` and `digital::Error`.
+    #[cfg(feature = "embedded-hal")]
+    #[allow(non_camel_case_types)]
+    pub struct Digital_t;
+
     pub use crate::error_category::Unused;
 }
 
 /// An error code belonging to an [`ErrorCategory`].
 ///
-/// Must only be 4 bits wide.
+/// Must only be 4 bits wide, unless it belongs to a [wide](ErrorCategory::WIDE) error
+/// category.
 pub type ErrorCode = u8;
 
 /// Derive [`ErrorCategory`] for an enum.
 ///
+/// This derive macro is only available with the `derive` feature (enabled by default),
+/// see [`error_category!`] for an alternative without proc-macros.
+///
 /// This will also derive the trait dependencies of
 /// [`ErrorCategory`](ErrorCategory) with the exception of
 /// [`Copy`](core::marker::Copy):
@@ -161,11 +249,49 @@ pub type ErrorCode = u8;
 /// - [`Into`](core::convert::Into)`<`[`ErrorCode`](ErrorCode)`>`
 /// - [`From`](core::convert::From)`<`[`ErrorCode`](ErrorCode)`>`
 ///
-/// The traits `Into<ErrorCode>` and `From<ErrorCode>` are only derived if the enum is
-/// `repr(u8)` (see
-/// [`Data Layout` in the
-/// nomicon](https://doc.rust-lang.org/nomicon/other-reprs.html#repru-repri)) or does *not*
-/// contain any variants.
+/// The error code of a variant is its discriminant, so the enum can have any integer
+/// `repr` (or none), but all discriminants must be in the range of an [`ErrorCode`]. The
+/// conversions are implemented using match tables, no `transmute` is used.
+///
+/// The enum can also be generic, and its generic parameters can be used in the links of
+/// the [`#[error_category]`](#error_category-attribute) attribute. Because an enum must
+/// use all of its generic parameters, a generic enum needs a marker variant with a
+/// [`core::convert::Infallible`] field (which can never be instantiated and has no error
+/// code). The error code of every other variant is then its index (see [Variants with
+/// fields](#variants-with-fields)):
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// use core::{convert::Infallible, marker::PhantomData};
+///
+/// trait Bus: Copy {
+///     type Error: ErrorCategory;
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(B::Error))]
+/// enum BusError<B: Bus> {
+///     Timeout,
+///     Nack,
+///     #[doc(hidden)]
+///     _Marker(Infallible, PhantomData<B>),
+/// }
+/// ```
+///
+/// An enum with only const generic parameters doesn't need a marker variant and keeps its
+/// discriminants as error codes. Their range is checked when an error code conversion is
+/// instantiated:
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::{prelude::*, ErrorCode};
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// enum ChannelError<const N: usize> {
+///     Overrun = 20,
+///     // error: `ChannelError::Overrun` variant discriminant must be less than 16
+/// }
+///
+/// let code: ErrorCode = ChannelError::<2>::Overrun.into();
+/// ```
 ///
 /// ## `#[error_category]` attribute
 /// This attribute is optionally put once on the enum that is to be derived. It specifies
@@ -175,13 +301,14 @@ pub type ErrorCode = u8;
 /// category](ErrorCategory) is not linked.
 ///
 /// **Example:**
-/// ```
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
 /// # use embedded_error_chain::prelude::*;
 /// #
 /// # #[derive(Clone, Copy, ErrorCategory)]
-/// # enum Type0 {}
+/// # enum Type0 { Error }
 /// # #[derive(Clone, Copy, ErrorCategory)]
-/// # enum Type1 {}
+/// # enum Type1 { Error }
 /// #
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// #[error_category(name = "CustomName", links(Type0, Type1))]
@@ -191,6 +318,75 @@ pub type ErrorCode = u8;
 /// }
 /// ```
 ///
+/// ### Wide categories
+/// The argument `wide` (`#[error_category(wide)]`) makes the category
+/// [wide](ErrorCategory::WIDE), so that its error codes can have a value from `0` to
+/// `255` instead of `0` to `15`. This is useful for wrapping status enums of vendor
+/// HALs. A wide error code can only be the most recent error code of an error chain,
+/// so a wide category can link other categories but can't be linked itself:
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(wide)]
+/// #[repr(u8)]
+/// enum HalError {
+///     Busy = 17,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(HalError))]
+/// //                     ~~~~~~~~
+/// // error: `HalError` is a wide error category and cannot be linked by `DriverError`
+/// #[repr(u8)]
+/// enum DriverError {
+///     InitFailed,
+/// }
+/// ```
+///
+/// ### Remote categories
+/// Enums of other crates (like the errors of a HAL) can't derive [`ErrorCategory`]. With
+/// the argument `remote = "path::to::Enum"`, the derived enum is a mirror of the foreign
+/// enum `path::to::Enum` with the same variant names (similar to serde's remote derive).
+/// The mirror is a regular error category that can start error chains and be used in
+/// `links(...)`, and [`From`] is implemented to convert the foreign enum into the mirror
+/// and back. If the foreign enum is `#[non_exhaustive]`, the mirror needs an
+/// `#[error(unknown)]` variant (see [Unknown error codes](#unknown-error-codes)) that all
+/// other values are converted to, and can't be converted back.
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// mod hal {
+///     pub mod spi {
+///         pub enum Error {
+///             Overrun,
+///             ModeFault,
+///         }
+///     }
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(remote = "hal::spi::Error")]
+/// #[repr(u8)]
+/// enum SpiError {
+///     /// overrun
+///     Overrun,
+///     /// mode fault
+///     ModeFault,
+/// }
+///
+/// fn spi_write() -> Result<(), hal::spi::Error> {
+///     Err(hal::spi::Error::ModeFault)
+/// }
+///
+/// fn init() -> Result<(), Error<SpiError>> {
+///     spi_write().map_err(SpiError::from)?;
+///     Ok(())
+/// }
+///
+/// assert_eq!(format!("{:?}", init().unwrap_err()), "SpiError(1): mode fault");
+/// ```
+///
 /// ## `#[error]` attribute
 /// This attribute is also optional and can be placed once above every enum variant.
 /// Its arguments specify the arguments used for debug printing of an error code
@@ -207,6 +403,11 @@ pub type ErrorCode = u8;
 /// - `{details}` will be replaced with the details section of the doc comments on the variant.
 /// - `{summary}` will be replaced with the summary of the doc comments on the variant.
 ///
+/// Additionally the named format argument `{value}` (also with an integer format spec,
+/// like `{value:#x}`) prints the [`Payload`] of an [`Error`] (see
+/// [`ErrorCategory::fmt_with_value()`]). If the error code is printed without a payload,
+/// `{value}` is printed as `?` (see [`PayloadValue::Missing`]).
+///
 /// The summary section of the doc comments is all non-empty lines, ignoring all empty
 /// lines until the first non-empty line, until an empty line or the end of the doc
 /// comments. All the summary lines are then trimmed for whitespace and joined using a
@@ -245,9 +446,157 @@ pub type ErrorCode = u8;
 /// comments will be used (see above). If the summary does not exist (no doc comments on
 /// the variant) or is empty, then the variant name is used for debug printing.
 ///
+/// ### Unknown error codes
+/// Error codes decoded from another firmware version may not exist in the enum. One
+/// unit variant can be marked with `#[error(unknown)]`, then [`From<ErrorCode>`] converts
+/// every error code that is not a variant of the enum to this variant instead of
+/// assuming that the code is valid. The original error code is kept in the [`Error`] (see
+/// [`Error::raw_code()`]) and is still printed:
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[non_exhaustive]
+/// #[repr(u8)]
+/// enum SpiError {
+///     /// bus error
+///     BusError,
+///     /// unknown
+///     #[error(unknown)]
+///     Unknown = 15,
+/// }
+///
+/// let err: Error<SpiError> = Error::new_raw(11);
+/// assert!(matches!(err.code(), SpiError::Unknown));
+/// assert_eq!(format!("{:?}", err), "SpiError(11): unknown");
+/// ```
+///
+/// Without an `#[error(unknown)]` variant, converting an invalid error code panics in
+/// debug builds and results in the last variant otherwise.
+///
+/// ### Mapping categories
+/// A submodule's `Error<SubError>` can become an `Error<ModuleError>` without adding an
+/// entry to the error chain, if every error code of `SubError` has a corresponding error
+/// code in `ModuleError`. The attribute `#[error(map(...))]` on a variant of `ModuleError`
+/// lists the variants of other categories that are converted into it, and derives
+/// [`FromCategory`] for every category that is mentioned. It can be used in addition to
+/// another `#[error(...)]` attribute.
+///
+/// All variants of a mapped category must be listed, otherwise they are converted to the
+/// `#[error(unknown)]` variant if there is one. Because the error chain is kept, it is
+/// checked at compile time that `ModuleError` links every category that `SubError` links,
+/// in the same order.
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// # use embedded_error_chain::ResultErrorExt;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum SubError {
+///     Timeout,
+///     Busy,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum ModuleError {
+///     #[error(map(SubError::Timeout, SubError::Busy))]
+///     NotReady,
+///     InvalidConfig,
+/// }
+///
+/// fn sub_init() -> Result<(), Error<SubError>> {
+///     Err(SpiError::BusError.chain(SubError::Busy))
+/// }
+///
+/// fn module_init() -> Result<(), Error<ModuleError>> {
+///     sub_init().map_category()
+/// }
+///
+/// let err = module_init().unwrap_err();
+/// assert_eq!(err.code(), ModuleError::NotReady);
+/// assert!(err.caused_by(SpiError::BusError));
+/// ```
+///
+/// Mapping a category whose links differ does not compile:
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum SubError {
+///     Timeout,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum ModuleError {
+///     #[error(map(SubError::Timeout))]
+///     NotReady,
+/// }
+/// ```
+///
+/// ## Variants with fields
+/// Variants can also be tuple variants with a single field, like `Timeout(u16)`. All
+/// fields must have the same type, which must implement [`Payload`] and [`Default`]. In
+/// this case the error code of every variant is its index (explicit discriminants are not
+/// allowed) and [`PayloadCategory`] is derived as
+/// well. The field is stored as the payload of an [`Error`] using
+/// [`Error::from_variant()`], and is lost when the error is chained. A chained error code
+/// of a variant that prints its field is printed using its doc summary (or its name if it
+/// has no doc comments) instead, so a field that is no longer known is never printed.
+///
+/// If the format string of a variant with a field uses positional arguments (like `{}`
+/// or `{0:#x}`), the field is passed as the first positional argument:
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// enum SensorError {
+///     #[error("timeout after {0} ms")]
+///     Timeout(u16),
+///     /// not connected
+///     NotConnected,
+/// }
+///
+/// let err = Error::from_variant(SensorError::Timeout(250));
+/// assert_eq!(format!("{:?}", err), "SensorError(0): timeout after 250 ms");
+/// assert_eq!(format!("{:?}", DynError::from(err)), "SensorError(0): Timeout");
+/// ```
+///
+/// ### Categories without variants
+/// An enum without variants can be used as a placeholder for a category that has no error
+/// codes yet. Converting an error code into it would have to panic, so it fails to compile
+/// if the feature `no-panic` is enabled:
+#[cfg_attr(all(feature = "derive", feature = "no-panic"), doc = "```compile_fail")]
+#[cfg_attr(not(all(feature = "derive", feature = "no-panic")), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// enum YetEmptyError {}
+/// ```
+///
 /// ## Full example
 ///
-/// ```rust
+#[cfg_attr(all(feature = "derive", not(feature = "no-panic")), doc = "```")]
+#[cfg_attr(
+    not(all(feature = "derive", not(feature = "no-panic"))),
+    doc = "```ignore"
+)]
 /// use embedded_error_chain::prelude::*;
 ///
 /// #[derive(Clone, Copy, ErrorCategory)]
@@ -290,4 +639,5 @@ pub type ErrorCode = u8;
 /// #[derive(Clone, Copy, ErrorCategory)]
 /// enum YetEmptyError {}
 /// ```
+#[cfg(feature = "derive")]
 pub use embedded_error_chain_macros::ErrorCategory;
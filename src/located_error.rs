@@ -0,0 +1,268 @@
+use crate::{marker, ChainError, DynError, Error, ErrorCategory, ERROR_CHAIN_LEN};
+#[cfg(feature = "location")]
+use core::fmt::Write;
+use core::{
+    fmt::{self, Debug, Formatter},
+    panic::Location,
+};
+
+/// The source locations of all entries in an error chain, most recent first.
+#[cfg(feature = "location")]
+type Locations = [Option<&'static Location<'static>>; ERROR_CHAIN_LEN + 1];
+
+/// An [`Error`] that additionally records the source location of every entry in its
+/// chain.
+///
+/// The location of an entry is the call site of [`LocatedError::new()`] or
+/// [`LocatedError::chain()`] which created the entry, or of the conversion into a
+/// [`LocatedError`] (for example by `?`). It is captured using `#[track_caller]` and
+/// printed as `at file:line` after every entry when debug formatting the error:
+/// ```txt
+/// GyroAccError(1): readout failed at src/gyro.rs:42
+/// - SpiError(0): bus error at src/spi.rs:17
+/// ```
+///
+/// Locations are only recorded if the feature `location` is enabled. If it is disabled,
+/// a [`LocatedError<C>`] is just an [`Error<C>`] of the same size, all locations are
+/// `None` and no code is generated for capturing them.
+///
+/// A [`LocatedError`] also implements [`ChainError`], so a `Result<T, LocatedError<C>>`
+/// can be chained with [`chain_err()`](crate::ResultChainError::chain_err()) like any
+/// other error. As that returns a plain [`Error`], the locations of the older entries are
+/// lost and only the location of the conversion back into a [`LocatedError`] is
+/// recorded. Entries that were chained before the error became a [`LocatedError`] have
+/// no location either. When a [`LocatedError`] is converted into an [`Error`] or
+/// [`DynError`] all locations are lost.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// # use embedded_error_chain::LocatedError;
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum GyroAccError {
+///     ReadoutFailed,
+/// }
+///
+/// fn spi_read() -> Result<u8, LocatedError<SpiError>> {
+///     Err(LocatedError::new(SpiError::BusError))
+/// }
+///
+/// fn gyro_acc_readout() -> Result<u8, LocatedError<GyroAccError>> {
+///     spi_read().map_err(|err| err.chain(GyroAccError::ReadoutFailed))
+/// }
+///
+/// fn gyro_acc_init() -> Result<(), LocatedError<GyroAccError>> {
+///     spi_read().chain_err(GyroAccError::ReadoutFailed)?;
+///     Ok(())
+/// }
+///
+/// let err = gyro_acc_readout().unwrap_err();
+/// assert_eq!(err.location().is_some(), cfg!(feature = "location"));
+/// assert_eq!(err.locations()[1].is_some(), cfg!(feature = "location"));
+///
+/// let err = gyro_acc_init().unwrap_err();
+/// assert_eq!(err.location().is_some(), cfg!(feature = "location"));
+/// assert!(err.locations()[1].is_none());
+/// ```
+#[cfg_attr(not(feature = "location"), repr(transparent))]
+pub struct LocatedError<C> {
+    error: Error<C>,
+    #[cfg(feature = "location")]
+    locations: Locations,
+}
+
+impl<C: ErrorCategory> LocatedError<C> {
+    /// Create a new [`LocatedError`] with an empty chain from the supplied `error_code`,
+    /// and record the location of the caller.
+    #[cfg_attr(feature = "location", track_caller)]
+    #[inline]
+    pub fn new(error_code: C) -> LocatedError<C> {
+        LocatedError::from_error(Error::new(error_code))
+    }
+
+    /// Create a new [`LocatedError`] from `error`, and record the location of the caller
+    /// for its most recent error code.
+    #[cfg_attr(feature = "location", track_caller)]
+    #[inline]
+    pub fn from_error(error: Error<C>) -> LocatedError<C> {
+        LocatedError {
+            error,
+            #[cfg(feature = "location")]
+            locations: push_front([None; ERROR_CHAIN_LEN + 1], 0, Location::caller()),
+        }
+    }
+
+    /// Get the error without the locations.
+    #[inline(always)]
+    pub fn error(&self) -> Error<C> {
+        self.error
+    }
+
+    /// Get the error code of the latest error.
+    #[inline]
+    pub fn code(&self) -> C {
+        self.error.code()
+    }
+
+    /// Get the source location of the latest error.
+    ///
+    /// Always returns `None` if the feature `location` is disabled.
+    #[inline]
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.locations()[0]
+    }
+
+    /// Get the source locations of all entries in the error chain, most recent first.
+    ///
+    /// All locations are `None` if the feature `location` is disabled.
+    #[inline]
+    pub fn locations(&self) -> [Option<&'static Location<'static>>; ERROR_CHAIN_LEN + 1] {
+        #[cfg(feature = "location")]
+        {
+            self.locations
+        }
+        #[cfg(not(feature = "location"))]
+        {
+            [None; ERROR_CHAIN_LEN + 1]
+        }
+    }
+
+    /// Chain this error with the supplied `error_code`, and record the location of the
+    /// caller for `error_code`.
+    ///
+    /// ### Panics
+    /// If the [error category](ErrorCategory) `O` is not linked with the [`ErrorCategory`]
+    /// of the most recent error code, this function will panic.
+    #[cfg_attr(feature = "location", track_caller)]
+    #[inline]
+    pub fn chain<O: ErrorCategory, Tag>(self, error_code: O) -> LocatedError<O>
+    where
+        Error<C>: ChainError<O, Tag>,
+    {
+        let error = self.error.chain(error_code);
+        LocatedError {
+            error,
+            #[cfg(feature = "location")]
+            locations: push_front(self.locations, error.chain_len(), Location::caller()),
+        }
+    }
+}
+
+/// Prepend `location` to `locations` and keep only the locations of the `chain_len`
+/// chained entries, the locations of entries that were dropped from the chain are lost.
+#[cfg(feature = "location")]
+#[inline]
+fn push_front(
+    locations: Locations,
+    chain_len: usize,
+    location: &'static Location<'static>,
+) -> Locations {
+    let mut new_locations = [None; ERROR_CHAIN_LEN + 1];
+    new_locations[0] = Some(location);
+    for (new_location, old_location) in new_locations[1..]
+        .iter_mut()
+        .zip(&locations)
+        .take(chain_len)
+    {
+        *new_location = *old_location;
+    }
+    new_locations
+}
+
+impl<O, Tag, C> ChainError<O, (Tag, marker::Located_t)> for LocatedError<C>
+where
+    O: ErrorCategory,
+    C: ErrorCategory,
+    Error<C>: ChainError<O, Tag>,
+{
+    /// Chain this error with the supplied `error_code` and return a plain [`Error`]
+    /// without the locations.
+    ///
+    /// Use the inherent [`LocatedError::chain()`] to keep the locations.
+    #[inline]
+    fn chain(self, error_code: O) -> Error<O> {
+        self.error.chain(error_code)
+    }
+}
+
+impl<C: ErrorCategory> Debug for LocatedError<C> {
+    /// Debug format this error and its chain, with the location of every entry.
+    ///
+    /// Error message example:
+    /// ```txt
+    /// GyroAccError(1): readout failed at src/gyro.rs:42
+    /// - SpiError(0): bus error at src/spi.rs:17
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "location")]
+        {
+            DynError::from(self.error).fmt_chain(f, None, |i, f| match self.locations[i] {
+                Some(location) => {
+                    f.write_str(" at ")?;
+                    f.write_str(location.file())?;
+                    f.write_char(':')?;
+                    Debug::fmt(&location.line(), f)
+                }
+                None => Ok(()),
+            })
+        }
+        #[cfg(not(feature = "location"))]
+        {
+            self.error.fmt(f)
+        }
+    }
+}
+
+impl<C: ErrorCategory> PartialEq for LocatedError<C> {
+    fn eq(&self, other: &LocatedError<C>) -> bool {
+        self.error == other.error && self.locations() == other.locations()
+    }
+}
+impl<C: ErrorCategory> Eq for LocatedError<C> {}
+
+impl<C: ErrorCategory> Clone for LocatedError<C> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: ErrorCategory> Copy for LocatedError<C> {}
+
+impl<C: ErrorCategory> From<C> for LocatedError<C> {
+    #[cfg_attr(feature = "location", track_caller)]
+    #[inline]
+    fn from(error_code: C) -> Self {
+        LocatedError::new(error_code)
+    }
+}
+
+impl<C: ErrorCategory> From<Error<C>> for LocatedError<C> {
+    #[cfg_attr(feature = "location", track_caller)]
+    #[inline]
+    fn from(error: Error<C>) -> Self {
+        LocatedError::from_error(error)
+    }
+}
+
+impl<C: ErrorCategory> From<LocatedError<C>> for Error<C> {
+    #[inline(always)]
+    fn from(error: LocatedError<C>) -> Self {
+        error.error
+    }
+}
+
+impl<C: ErrorCategory> From<LocatedError<C>> for DynError {
+    #[inline]
+    fn from(error: LocatedError<C>) -> Self {
+        error.error.into()
+    }
+}
@@ -0,0 +1,427 @@
+/// Declare an enum and implement [`ErrorCategory`](crate::ErrorCategory) for it, without
+/// using the proc-macro derive.
+///
+/// This macro is an alternative to [`#[derive(ErrorCategory)]`](derive@crate::ErrorCategory)
+/// for environments that avoid proc-macros. It generates the same [`ErrorCategory`]
+/// implementation, conversions from and into [`ErrorCode`](crate::ErrorCode), [`Debug`]
+/// implementation and compile-time checks, and supports the following subset of the
+/// derive macro's syntax:
+///
+/// - An optional `#[error_category(...)]` attribute with the arguments `name = "..."`,
+///   `links(...)` and `wide` (in any order). It must come before all other attributes of
+///   the enum except doc comments.
+/// - Unit variants with optional explicit discriminants.
+/// - Doc comments on variants, of which the summary is used for debug printing. Like with
+///   the derive macro, the summary is used as a format string: `{{` and `}}` print `{`
+///   and `}`, and the placeholders `{category}`, `{variant}` and `{value}` are replaced.
+///   `{value}` supports format specs made of the flags `#` and `0`, a width and an
+///   integer formatting trait, like `{value:#04x}`.
+/// - An optional `#[error(...)]` attribute after the doc comments of a variant. Its
+///   arguments are passed to [`write!()`] unchanged, so the placeholders `{summary}`,
+///   `{details}`, `{variant}`, `{category}` and `{value}` are not supported.
+///
+/// Variants with fields, generic enums and `#[error(unknown)]` are not supported. An enum
+/// without variants fails to compile if the feature `no-panic` is enabled.
+///
+/// ```
+/// use embedded_error_chain::{error_category, prelude::*};
+///
+/// error_category! {
+///     #[derive(Clone, Copy)]
+///     #[repr(u8)]
+///     pub enum SpiError {
+///         /// bus error
+///         BusError,
+///     }
+/// }
+///
+/// error_category! {
+///     /// Errors of the gyroscope and accelerometer driver.
+///     #[error_category(name = "GyroAcc", links(SpiError))]
+///     #[derive(Clone, Copy)]
+///     #[repr(u8)]
+///     pub enum GyroAccError {
+///         /// init failed
+///         InitFailed,
+///         #[error("readout failed after {} retries", 3)]
+///         ReadoutFailed = 4,
+///     }
+/// }
+///
+/// let err = SpiError::BusError.chain(GyroAccError::ReadoutFailed);
+/// assert_eq!(
+///     format!("{:?}", err),
+///     "GyroAcc(4): readout failed after 3 retries\n- SpiError(0): bus error"
+/// );
+/// ```
+///
+/// [`ErrorCategory`]: crate::ErrorCategory
+/// [`Debug`]: core::fmt::Debug
+#[macro_export]
+macro_rules! error_category {
+    (
+        $(#[doc = $enum_doc:literal])*
+        #[error_category($($args:tt)*)]
+        $($enum:tt)*
+    ) => {
+        $crate::error_category!(
+            @args [] [] [] ($($args)*)
+            $(#[doc = $enum_doc])*
+            $($enum)*
+        );
+    };
+
+    // Parse the arguments of the `#[error_category(...)]` attribute.
+    (@args [] [$($links:tt)*] [$($wide:tt)*] (name = $name:literal $(, $($rest:tt)*)?) $($enum:tt)*) => {
+        $crate::error_category!(@args [$name] [$($links)*] [$($wide)*] ($($($rest)*)?) $($enum)*);
+    };
+    (@args [$($name:tt)*] [] [$($wide:tt)*] (links($($link:ty),* $(,)?) $(, $($rest:tt)*)?) $($enum:tt)*) => {
+        $crate::error_category!(@args [$($name)*] [$($link),*] [$($wide)*] ($($($rest)*)?) $($enum)*);
+    };
+    (@args [$($name:tt)*] [$($links:tt)*] [] (wide $(, $($rest:tt)*)?) $($enum:tt)*) => {
+        $crate::error_category!(@args [$($name)*] [$($links)*] [wide] ($($($rest)*)?) $($enum)*);
+    };
+    (@args [$($name:tt)*] [$($links:tt)*] [$($wide:tt)*] () $($enum:tt)*) => {
+        $crate::error_category!(@impl [$($name)*] [$($links)*] [$($wide)*] $($enum)*);
+    };
+    (@args [$($name:tt)*] [$($links:tt)*] [$($wide:tt)*] ($($invalid:tt)*) $($enum:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "invalid `#[error_category(...)]` arguments `",
+            ::core::stringify!($($invalid)*),
+            "`, expected at most one of each `name = \"...\"`, `links(...)` and `wide`"
+        ));
+    };
+
+    (@impl [$($cat_name:literal)?] [$($link:ty),*] [$($wide:ident)?]
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[doc = $doc:literal])*
+                $(#[error($($fmt:tt)+)])?
+                $variant:ident $(= $discriminant:expr)?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                $(#[doc = $doc])*
+                $variant $(= $discriminant)?,
+            )*
+        }
+
+        $crate::error_category!(@discriminant_checks [$($wide)?] $name [$($variant),*]);
+        $(
+            $crate::const_assert!(
+                !<$link as $crate::ErrorCategory>::WIDE,
+                ::core::concat!(
+                    "`", ::core::stringify!($link),
+                    "` is a wide error category and cannot be linked by `",
+                    ::core::stringify!($name), "`"
+                )
+            );
+        )*
+        $crate::const_assert!(
+            <[&str]>::len(&[$(::core::stringify!($link)),*]) <= 6,
+            "too many links, at most 6 links are allowed"
+        );
+
+        impl $crate::ErrorCategory for $name {
+            const NAME: &'static str = $crate::error_category!(@name [$($cat_name)?] $name);
+            const WIDE: bool = $crate::error_category!(@wide [$($wide)?]);
+
+            type L0 = $crate::error_category!(@link [] $($link),*);
+            type L1 = $crate::error_category!(@link [_] $($link),*);
+            type L2 = $crate::error_category!(@link [_ _] $($link),*);
+            type L3 = $crate::error_category!(@link [_ _ _] $($link),*);
+            type L4 = $crate::error_category!(@link [_ _ _ _] $($link),*);
+            type L5 = $crate::error_category!(@link [_ _ _ _ _] $($link),*);
+
+            fn chainable_category_formatters() -> &'static [$crate::ErrorCodeFormatter] {
+                &[$($crate::format_chained::<$link>),*]
+            }
+
+            fn fmt_with_value(
+                &self,
+                value: $crate::PayloadValue,
+                f: &mut $crate::utils::fmt::Formatter<'_>,
+            ) -> $crate::utils::fmt::Result {
+                match *self {
+                    $(
+                        $name::$variant => $crate::error_category!(
+                            @fmt f value $variant [$($($fmt)+)?] [$($doc),*]
+                        ),
+                    )*
+                }
+            }
+        }
+
+        impl $crate::utils::From<$crate::ErrorCode> for $name {
+            fn from(val: $crate::ErrorCode) -> Self {
+                $crate::error_category!(@from_error_code val $name [$($variant),*])
+            }
+        }
+
+        impl $crate::utils::From<$name> for $crate::ErrorCode {
+            fn from(val: $name) -> $crate::ErrorCode {
+                $crate::error_category!(@into_error_code val [$($variant),*])
+            }
+        }
+
+        impl $crate::utils::Debug for $name {
+            fn fmt(&self, f: &mut $crate::utils::fmt::Formatter<'_>) -> $crate::utils::fmt::Result {
+                $crate::ErrorCategory::fmt_with_value(self, $crate::PayloadValue::Missing, f)
+            }
+        }
+    };
+
+    (@name [] $name:ident) => { ::core::stringify!($name) };
+    (@name [$cat_name:literal] $name:ident) => { $cat_name };
+
+    (@wide []) => { false };
+    (@wide [wide]) => { true };
+
+    // Get the link with the index given by the number of `_` tokens, or `Unused`.
+    (@link [] $link:ty $(, $rest:ty)*) => { $link };
+    (@link [_ $($n:tt)*] $link:ty $(, $rest:ty)*) => {
+        $crate::error_category!(@link [$($n)*] $($rest),*)
+    };
+    (@link [$($n:tt)*]) => { $crate::marker::Unused };
+
+    (@discriminant_checks [] $name:ident [$($variant:ident),*]) => {
+        $(
+            $crate::const_assert!(
+                ($name::$variant as isize) >= 0,
+                ::core::concat!(
+                    "`", ::core::stringify!($name), "::", ::core::stringify!($variant),
+                    "` variant discriminant must not be negative"
+                )
+            );
+            $crate::const_assert!(
+                ($name::$variant as isize) < 16,
+                ::core::concat!(
+                    "`", ::core::stringify!($name), "::", ::core::stringify!($variant),
+                    "` variant discriminant must be less than 16 (use `#[error_category(wide)]` for larger discriminants)"
+                )
+            );
+        )*
+    };
+    (@discriminant_checks [wide] $name:ident [$($variant:ident),*]) => {
+        $(
+            $crate::const_assert!(
+                ($name::$variant as isize) >= 0,
+                ::core::concat!(
+                    "`", ::core::stringify!($name), "::", ::core::stringify!($variant),
+                    "` variant discriminant must not be negative"
+                )
+            );
+            $crate::const_assert!(
+                ($name::$variant as isize) < 256,
+                ::core::concat!(
+                    "`", ::core::stringify!($name), "::", ::core::stringify!($variant),
+                    "` variant discriminant must be less than 256"
+                )
+            );
+        )*
+    };
+
+    // The error code of every variant is its discriminant, which is converted back to the
+    // variant using a match table.
+    (@from_error_code $val:ident $name:ident []) => {
+        match $val {
+            _ => $crate::uninhabited_error_code!($name),
+        }
+    };
+    (@from_error_code $val:ident $name:ident [$($variant:ident),+]) => {
+        match $val {
+            $(_ if $val == $name::$variant as $crate::ErrorCode => $name::$variant,)+
+            _ => {
+                $crate::utils::invalid_error_code();
+                $crate::error_category!(@last $name $($variant)+)
+            }
+        }
+    };
+
+    (@last $name:ident $variant:ident) => { $name::$variant };
+    (@last $name:ident $variant:ident $($rest:ident)+) => {
+        $crate::error_category!(@last $name $($rest)+)
+    };
+
+    (@into_error_code $val:ident []) => {
+        match $val {}
+    };
+    (@into_error_code $val:ident [$($variant:ident),+]) => {
+        $val as $crate::ErrorCode
+    };
+
+    (@fmt $f:ident $value:ident $variant:ident [] [$($doc:literal),*]) => {
+        $crate::utils::fmt_doc_summary(
+            $f,
+            &[$($doc),*],
+            <Self as $crate::ErrorCategory>::NAME,
+            ::core::stringify!($variant),
+            $value,
+        )
+    };
+    (@fmt $f:ident $value:ident $variant:ident [$($fmt:tt)+] [$($doc:literal),*]) => {{
+        let _ = $value;
+        ::core::write!($f, $($fmt)+)
+    }};
+
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $($enum:tt)*
+    ) => {
+        $crate::error_category!(@impl [] [] [] $(#[$attr])* $vis enum $($enum)*);
+    };
+}
+
+/// Match the error codes in the chain of an error against a list of arms.
+///
+/// The first argument is the error, which can be anything with an `iter()` method that
+/// iterates over its error codes, like [`Error`](crate::Error) or
+/// [`DynError`](crate::DynError). It is only borrowed. The arms are checked in order, and
+/// the expression of the first arm that matches is evaluated:
+///
+/// - `Category::Code => expr` matches if any entry in the error chain is the error code
+///   `Category::Code`.
+/// - `Category::_ => expr` matches if any entry in the error chain belongs to the
+///   [`ErrorCategory`](crate::ErrorCategory) `Category`.
+/// - Prefixing one of the above with `top:` only matches against the most recent error
+///   code of the error.
+/// - Several of the above can be combined with `|`, the arm matches if any of them
+///   matches.
+/// - `_ => expr` must be the last arm and matches everything else.
+///
+/// Like in a `match`, the comma after an arm can be omitted if its expression is a block.
+///
+/// Every arm scans the chain using `iter()` until it finds a matching entry. Arms that do
+/// not name an error code or category are rejected at compile time.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use embedded_error_chain::{match_error, prelude::*};
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SpiError {
+///     BusError,
+///     Timeout,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SpiError))]
+/// #[repr(u8)]
+/// enum GyroAccError {
+///     InitFailed,
+///     ReadoutFailed,
+///     Overheated,
+/// }
+///
+/// fn recovery(err: Error<GyroAccError>) -> &'static str {
+///     match_error!(err, {
+///         SpiError::Timeout => "retry",
+///         top: GyroAccError::InitFailed | GyroAccError::Overheated => "reset",
+///         SpiError::_ => "reset the bus",
+///         _ => {
+///             // Nothing left to try.
+///             "give up"
+///         }
+///     })
+/// }
+///
+/// assert_eq!(recovery(SpiError::Timeout.chain(GyroAccError::ReadoutFailed)), "retry");
+/// assert_eq!(recovery(SpiError::BusError.chain(GyroAccError::InitFailed)), "reset");
+/// assert_eq!(recovery(SpiError::BusError.chain(GyroAccError::ReadoutFailed)), "reset the bus");
+/// assert_eq!(recovery(Error::new(GyroAccError::Overheated)), "reset");
+/// assert_eq!(recovery(Error::new(GyroAccError::ReadoutFailed)), "give up");
+/// ```
+///
+#[cfg_attr(feature = "derive", doc = "```compile_fail")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::{match_error, prelude::*};
+/// # #[derive(Clone, Copy, ErrorCategory)]
+/// # #[repr(u8)]
+/// # enum SpiError {
+/// #     BusError,
+/// # }
+/// let err = Error::new(SpiError::BusError);
+/// match_error!(err, {
+///     // `Option::None` is not an error code.
+///     Option::None => 1,
+///     _ => 0,
+/// });
+/// ```
+#[macro_export]
+macro_rules! match_error {
+    ($err:expr, { $($arms:tt)* }) => {{
+        let err = &$err;
+        $crate::match_error!(@arms err; $($arms)*)
+    }};
+
+    (@arms $err:ident; _ => $e:expr $(,)?) => {
+        $e
+    };
+    (@arms $err:ident; $($arms:tt)+) => {
+        $crate::match_error!(@pat $err; []; $($arms)+)
+    };
+
+    // Parse the patterns of an arm into a list of conditions.
+    (@pat $err:ident; [$($cond:expr),*]; top: $($category:ident)::+ :: _ $($rest:tt)*) => {
+        $crate::match_error!(@next $err; [
+            $($cond,)* $crate::utils::top_is_category::<$($category)::+>($err.iter())
+        ]; $($rest)*)
+    };
+    (@pat $err:ident; [$($cond:expr),*]; top: $code:path | $($rest:tt)*) => {
+        $crate::match_error!(@pat $err; [
+            $($cond,)* $crate::utils::top_is_code($err.iter(), $code)
+        ]; $($rest)*)
+    };
+    (@pat $err:ident; [$($cond:expr),*]; top: $code:path => $($rest:tt)*) => {
+        $crate::match_error!(@body $err; [
+            $($cond,)* $crate::utils::top_is_code($err.iter(), $code)
+        ]; $($rest)*)
+    };
+    (@pat $err:ident; [$($cond:expr),*]; $($category:ident)::+ :: _ $($rest:tt)*) => {
+        $crate::match_error!(@next $err; [
+            $($cond,)* $crate::utils::chain_has_category::<$($category)::+>($err.iter())
+        ]; $($rest)*)
+    };
+    (@pat $err:ident; [$($cond:expr),*]; $code:path | $($rest:tt)*) => {
+        $crate::match_error!(@pat $err; [
+            $($cond,)* $crate::utils::chain_has_code($err.iter(), $code)
+        ]; $($rest)*)
+    };
+    (@pat $err:ident; [$($cond:expr),*]; $code:path => $($rest:tt)*) => {
+        $crate::match_error!(@body $err; [
+            $($cond,)* $crate::utils::chain_has_code($err.iter(), $code)
+        ]; $($rest)*)
+    };
+    (@next $err:ident; [$($cond:expr),*]; | $($rest:tt)*) => {
+        $crate::match_error!(@pat $err; [$($cond),*]; $($rest)*)
+    };
+    (@next $err:ident; [$($cond:expr),*]; => $($rest:tt)*) => {
+        $crate::match_error!(@body $err; [$($cond),*]; $($rest)*)
+    };
+
+    // Parse the expression of an arm, which doesn't need a trailing comma if it's a block.
+    (@body $err:ident; [$($cond:expr),*]; $e:block, $($rest:tt)*) => {
+        $crate::match_error!(@if $err; [$($cond),*]; $e; $($rest)*)
+    };
+    (@body $err:ident; [$($cond:expr),*]; $e:block $($rest:tt)*) => {
+        $crate::match_error!(@if $err; [$($cond),*]; $e; $($rest)*)
+    };
+    (@body $err:ident; [$($cond:expr),*]; $e:expr, $($rest:tt)*) => {
+        $crate::match_error!(@if $err; [$($cond),*]; $e; $($rest)*)
+    };
+    (@if $err:ident; [$($cond:expr),*]; $e:expr; $($rest:tt)*) => {
+        if $($cond)||* {
+            $e
+        } else {
+            $crate::match_error!(@arms $err; $($rest)*)
+        }
+    };
+    (@arms $err:ident;) => {
+        ::core::compile_error!("`match_error!` requires a final `_ => ...` arm")
+    };
+}
@@ -0,0 +1,145 @@
+use crate::{ChainError, Error, ErrorCategory};
+
+/// A trait that allows chaining if a non-blocking [`nb::Result`] contains an error value.
+///
+/// Unlike [`ResultChainError`](crate::ResultChainError), only [`nb::Error::Other`] is
+/// chained, [`nb::Error::WouldBlock`] is forwarded unchanged so that polling the
+/// operation again still works.
+///
+/// The method is named differently from
+/// [`chain_err()`](crate::ResultChainError::chain_err()), because both traits would
+/// otherwise apply to an [`nb::Result`] and calls would be ambiguous.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// use embedded_error_chain::NbResultChainError;
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SerialError {
+///     Overrun,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SerialError))]
+/// #[repr(u8)]
+/// enum ModemError {
+///     ReadFailed,
+/// }
+///
+/// fn serial_read(ready: bool) -> nb::Result<u8, SerialError> {
+///     if ready {
+///         Err(nb::Error::Other(SerialError::Overrun))
+///     } else {
+///         Err(nb::Error::WouldBlock)
+///     }
+/// }
+///
+/// fn modem_read(ready: bool) -> nb::Result<u8, Error<ModemError>> {
+///     serial_read(ready).chain_err_nb(ModemError::ReadFailed)
+/// }
+///
+/// assert!(matches!(modem_read(false), Err(nb::Error::WouldBlock)));
+/// match modem_read(true) {
+///     Err(nb::Error::Other(err)) => assert!(err.caused_by(SerialError::Overrun)),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub trait NbResultChainError<T, O: ErrorCategory, Tag> {
+    /// If the result contains an [`nb::Error::Other`] value, chain it with the supplied
+    /// `error_code` and return [`nb::Error::Other`] with the result, otherwise forward the
+    /// [`Ok`] or [`nb::Error::WouldBlock`] value.
+    ///
+    /// ### Panics
+    /// If this result is an [`nb::Error::Other`] value and the [error
+    /// category](ErrorCategory) `O` is not linked with the [`ErrorCategory`] of the most
+    /// recent error code in the error, this function will panic.
+    fn chain_err_nb(self, error_code: O) -> nb::Result<T, Error<O>>;
+}
+
+impl<OK, ERR, O, TAG> NbResultChainError<OK, O, TAG> for nb::Result<OK, ERR>
+where
+    O: ErrorCategory,
+    ERR: ChainError<O, TAG>,
+{
+    #[inline]
+    fn chain_err_nb(self, error_code: O) -> nb::Result<OK, Error<O>> {
+        match self {
+            Err(nb::Error::Other(err)) => Err(nb::Error::Other(err.chain(error_code))),
+            Err(nb::Error::WouldBlock) => Err(nb::Error::WouldBlock),
+            Ok(val) => Ok(val),
+        }
+    }
+}
+
+/// Turn a non-blocking expression into a blocking one that returns an [`Error`].
+///
+/// Like `nb::block!`, this macro evaluates the expression repeatedly as long as it returns
+/// [`nb::Error::WouldBlock`]. Once it returns [`Ok`] or [`nb::Error::Other`], the macro
+/// returns a [`Result`] containing either the value or the error:
+///
+/// - `block_err!(expr)` wraps the error in a new [`Error`], so `expr` must return an
+///   error that implements [`ErrorCategory`].
+/// - `block_err!(expr, error_code)` [chains](ChainError::chain()) the error with
+///   `error_code`.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// # use embedded_error_chain::prelude::*;
+/// use embedded_error_chain::block_err;
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[repr(u8)]
+/// enum SerialError {
+///     Overrun,
+/// }
+///
+/// #[derive(Clone, Copy, ErrorCategory)]
+/// #[error_category(links(SerialError))]
+/// #[repr(u8)]
+/// enum ModemError {
+///     ReadFailed,
+/// }
+///
+/// let mut polls = 0;
+/// let mut serial_read = || -> nb::Result<u8, SerialError> {
+///     polls += 1;
+///     match polls {
+///         1 | 2 => Err(nb::Error::WouldBlock),
+///         3 => Ok(42),
+///         _ => Err(nb::Error::Other(SerialError::Overrun)),
+///     }
+/// };
+///
+/// let val: Result<u8, Error<SerialError>> = block_err!(serial_read());
+/// assert_eq!(val, Ok(42));
+///
+/// let err = block_err!(serial_read(), ModemError::ReadFailed).unwrap_err();
+/// assert!(err.caused_by(SerialError::Overrun));
+/// ```
+#[macro_export]
+macro_rules! block_err {
+    ($e:expr) => {
+        loop {
+            match $e {
+                ::core::result::Result::Err($crate::utils::nb::Error::Other(err)) => {
+                    break ::core::result::Result::Err($crate::Error::new(err));
+                }
+                ::core::result::Result::Err($crate::utils::nb::Error::WouldBlock) => {}
+                ::core::result::Result::Ok(val) => break ::core::result::Result::Ok(val),
+            }
+        }
+    };
+    ($e:expr, $error_code:expr) => {
+        loop {
+            match $e {
+                ::core::result::Result::Err($crate::utils::nb::Error::Other(err)) => {
+                    break ::core::result::Result::Err($crate::ChainError::chain(err, $error_code));
+                }
+                ::core::result::Result::Err($crate::utils::nb::Error::WouldBlock) => {}
+                ::core::result::Result::Ok(val) => break ::core::result::Result::Ok(val),
+            }
+        }
+    };
+}
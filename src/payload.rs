@@ -0,0 +1,140 @@
+use crate::{ErrorCategory, ErrorCode, ErrorData};
+use core::fmt::{self, Binary, Debug, Display, Formatter, LowerHex, Octal, UpperHex};
+
+/// A small integer value that can be attached to the most recent error code of an
+/// [`Error`](crate::Error).
+///
+/// The payload is printed using the `{value}` format argument of the `#[error(...)]`
+/// attribute (see [`ErrorCategory::fmt_with_value()`](crate::ErrorCategory::fmt_with_value())).
+/// `()` is the empty payload, which is never printed and does not increase the size of
+/// an [`Error`](crate::Error).
+pub trait Payload: Copy + Eq {
+    /// The [`ErrorData`] together with this payload, as stored in an
+    /// [`Error`](crate::Error).
+    #[doc(hidden)]
+    type Storage: Copy + Eq;
+
+    /// Get this payload as a formattable value, or `None` if there is nothing to format.
+    fn value(&self) -> Option<PayloadValue>;
+
+    #[doc(hidden)]
+    fn store(data: ErrorData, payload: Self) -> Self::Storage;
+
+    #[doc(hidden)]
+    fn load(storage: &Self::Storage) -> (ErrorData, Self);
+
+    #[doc(hidden)]
+    fn data_mut(storage: &mut Self::Storage) -> &mut ErrorData;
+}
+
+impl Payload for () {
+    type Storage = ErrorData;
+
+    #[inline(always)]
+    fn value(&self) -> Option<PayloadValue> {
+        None
+    }
+
+    #[inline(always)]
+    fn store(data: ErrorData, _: ()) -> ErrorData {
+        data
+    }
+
+    #[inline(always)]
+    fn load(storage: &ErrorData) -> (ErrorData, ()) {
+        (*storage, ())
+    }
+
+    #[inline(always)]
+    fn data_mut(storage: &mut ErrorData) -> &mut ErrorData {
+        storage
+    }
+}
+
+macro_rules! impl_payload {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl Payload for $t {
+                type Storage = (ErrorData, $t);
+
+                #[inline(always)]
+                fn value(&self) -> Option<PayloadValue> {
+                    Some(PayloadValue::$variant((*self).into()))
+                }
+
+                #[inline(always)]
+                fn store(data: ErrorData, payload: $t) -> (ErrorData, $t) {
+                    (data, payload)
+                }
+
+                #[inline(always)]
+                fn load(storage: &(ErrorData, $t)) -> (ErrorData, $t) {
+                    *storage
+                }
+
+                #[inline(always)]
+                fn data_mut(storage: &mut (ErrorData, $t)) -> &mut ErrorData {
+                    &mut storage.0
+                }
+            }
+        )*
+    };
+}
+
+impl_payload!(Unsigned: u8, u16, u32);
+impl_payload!(Signed: i8, i16, i32);
+
+/// An [`ErrorCategory`] with variants that contain a field.
+///
+/// This trait is implemented by the derive macro for enums with tuple variants like
+/// `Timeout(u16)`. The error code of such a variant only identifies the variant, its field
+/// is stored separately as the [`Payload`] of an [`Error`](crate::Error) (see
+/// [`Error::from_variant()`](crate::Error::from_variant())). Converting an error code
+/// without its payload into the category (using [`From<ErrorCode>`]) fills the field with
+/// its [`Default`] value.
+pub trait PayloadCategory: ErrorCategory {
+    /// The type of the field of all variants with a field.
+    type Payload: Payload + Default;
+
+    /// Get the field of this variant, or the default value if this variant has no field.
+    fn payload(&self) -> Self::Payload;
+
+    /// Reconstruct the variant of `error_code` with `payload` as its field.
+    ///
+    /// If the variant has no field, `payload` is ignored.
+    fn from_payload(error_code: ErrorCode, payload: Self::Payload) -> Self;
+}
+
+/// The value of a [`Payload`] passed to
+/// [`ErrorCategory::fmt_with_value()`](crate::ErrorCategory::fmt_with_value()).
+///
+/// This type implements all integer formatting traits ([`Display`], [`Debug`],
+/// [`LowerHex`], [`UpperHex`], [`Octal`] and [`Binary`]), so that the `{value}` format
+/// argument can have any integer format spec (like `{value:#04x}`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PayloadValue {
+    /// No payload is present, formatted as `?`.
+    Missing,
+    /// An unsigned payload.
+    Unsigned(u32),
+    /// A signed payload.
+    Signed(i32),
+}
+
+macro_rules! impl_fmt {
+    ($($trait:ident),*) => {
+        $(
+            impl $trait for PayloadValue {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    match self {
+                        PayloadValue::Missing => f.write_str("?"),
+                        PayloadValue::Unsigned(val) => $trait::fmt(val, f),
+                        PayloadValue::Signed(val) => $trait::fmt(val, f),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_fmt!(Display, Debug, LowerHex, UpperHex, Octal, Binary);
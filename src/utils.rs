@@ -1,4 +1,5 @@
-//! Utilities used by the proc-macros.
+//! Utilities used by the proc-macros and the [`error_category!`](crate::error_category!)
+//! macro.
 //!
 //! **This module has no stability guarantees.**
 
@@ -11,7 +12,7 @@ pub const fn const_assert(cond: bool, msg: &'static str) {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! const_assert {
-    ($cond:expr, $msg:literal) => {
+    ($cond:expr, $msg:expr) => {
         const _: () = $crate::utils::const_assert($cond, $msg);
     };
 }
@@ -20,14 +21,9 @@ macro_rules! const_assert {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! const_assert {
-    ($exp:expr, $msg:literal) => {
-        #[deny(const_err)]
-        #[allow(unused_must_use)]
-        const _: () = {
-            const ASSERT: bool = $exp;
-            ASSERT as usize - 1usize;
-
-            ()
+    ($exp:expr, $msg:expr) => {
+        const _: () = if !$exp {
+            ::core::panic!("{}", $msg)
         };
     };
 }
@@ -53,3 +49,333 @@ mod types {
 }
 
 pub use types::*;
+
+use crate::PayloadValue;
+
+#[cfg(feature = "nb")]
+pub use nb;
+
+/// Called when an error code without a variant is converted to a category that has no
+/// `#[error(unknown)]` variant, before the last variant is returned instead.
+///
+/// Panics in debug builds, unless the feature `no-panic` is enabled.
+#[inline(always)]
+pub fn invalid_error_code() {
+    #[cfg(not(feature = "no-panic"))]
+    debug_assert!(
+        false,
+        "tried to convert invalid error code to category type"
+    );
+}
+
+/// Used when an error code is converted to the category `$name` without variants, which
+/// has no value that could be returned.
+///
+/// Panics, or fails to compile if the feature `no-panic` is enabled.
+#[cfg(not(feature = "no-panic"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! uninhabited_error_code {
+    ($name:ident) => {
+        ::core::unreachable!(::core::concat!(
+            "tried to convert an error code to `",
+            ::core::stringify!($name),
+            "`, which has no variants"
+        ))
+    };
+}
+
+#[cfg(feature = "no-panic")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! uninhabited_error_code {
+    ($name:ident) => {
+        ::core::compile_error!(::core::concat!(
+            "error category `",
+            ::core::stringify!($name),
+            "` must have at least one variant with the feature `no-panic`"
+        ))
+    };
+}
+
+/// Write the summary of the doc comment lines `doc` of the variant `variant_name` of the
+/// category `category_name` to `f`, or `variant_name` if the summary is empty.
+///
+/// The summary is extracted the same way as by the derive macro: all lines until the
+/// first empty line (ignoring empty lines at the start), trimmed and joined using a
+/// space character. Like by the derive macro, the summary is then used as a format string
+/// with the arguments `category_name`, `variant_name` and `value` (see the
+/// [`error_category!`](crate::error_category!) macro).
+pub fn fmt_doc_summary(
+    f: &mut fmt::Formatter<'_>,
+    doc: &[&str],
+    category_name: &str,
+    variant_name: &str,
+    value: PayloadValue,
+) -> fmt::Result {
+    let mut lines = doc
+        .iter()
+        .flat_map(|doc| split_lines(doc))
+        .map(trim_whitespace)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty());
+
+    match lines.next() {
+        Some(first_line) => {
+            fmt_summary_line(f, first_line, category_name, variant_name, value)?;
+            for line in lines {
+                f.write_str(" ")?;
+                fmt_summary_line(f, line, category_name, variant_name, value)?;
+            }
+            Ok(())
+        }
+        None => f.write_str(variant_name),
+    }
+}
+
+/// Write the summary line `line` to `f`, using it as a format string like the derive
+/// macro does.
+///
+/// `{{` and `}}` are written as `{` and `}`, `{category}` and `{variant}` are replaced
+/// with `category_name` and `variant_name`, and `{value}` (also with an integer format
+/// spec like `{value:#04x}`) is replaced with `value`. Any other argument is written
+/// unchanged, where the derive macro fails to compile.
+fn fmt_summary_line(
+    f: &mut fmt::Formatter<'_>,
+    line: &str,
+    category_name: &str,
+    variant_name: &str,
+    value: PayloadValue,
+) -> fmt::Result {
+    let mut rest = line.as_bytes();
+    while !rest.is_empty() {
+        let (text, tail) = split_before(rest, |b| b == b'{' || b == b'}');
+        write_bytes(f, text)?;
+        rest = match tail {
+            [b'{', b'{', tail @ ..] => {
+                f.write_str("{")?;
+                tail
+            }
+            [b'}', b'}', tail @ ..] => {
+                f.write_str("}")?;
+                tail
+            }
+            [b'{', tail @ ..] => match split_before(tail, |b| b == b'}') {
+                (arg, [_, tail @ ..]) => {
+                    let result = match arg {
+                        b"category" => Some(f.write_str(category_name)),
+                        b"variant" => Some(f.write_str(variant_name)),
+                        [b'v', b'a', b'l', b'u', b'e', spec @ ..] => fmt_value(f, value, spec),
+                        _ => None,
+                    };
+                    match result {
+                        Some(result) => result?,
+                        None => {
+                            f.write_str("{")?;
+                            write_bytes(f, arg)?;
+                            f.write_str("}")?;
+                        }
+                    }
+                    tail
+                }
+                _ => {
+                    f.write_str("{")?;
+                    tail
+                }
+            },
+            [b'}', tail @ ..] => {
+                f.write_str("}")?;
+                tail
+            }
+            _ => &[],
+        };
+    }
+    Ok(())
+}
+
+/// Write `value` to `f` using the format spec `spec` (including the leading `:`), which
+/// supports the flags `#` and `0`, a width and all integer formatting traits.
+///
+/// Return `None` without writing anything if `spec` is not supported.
+fn fmt_value(f: &mut fmt::Formatter<'_>, value: PayloadValue, spec: &[u8]) -> Option<fmt::Result> {
+    let spec = match spec {
+        [] => return Some(fmt::Display::fmt(&value, f)),
+        [b':', spec @ ..] => spec,
+        _ => return None,
+    };
+    let (alternate, spec) = match spec {
+        [b'#', spec @ ..] => (true, spec),
+        _ => (false, spec),
+    };
+    let (zero, spec) = match spec {
+        [b'0', spec @ ..] => (true, spec),
+        _ => (false, spec),
+    };
+    let (digits, ty) = split_before(spec, |b| !b.is_ascii_digit());
+    // Format spec widths are limited to `u16`, which also keeps the formatting machinery
+    // from panicking on an overly large width.
+    let mut width = 0u16;
+    for digit in digits {
+        width = width
+            .checked_mul(10)?
+            .checked_add(u16::from(digit.wrapping_sub(b'0')))?;
+    }
+    let width = usize::from(width);
+
+    macro_rules! write_value {
+        ($ty:literal) => {
+            match (alternate, zero) {
+                (false, false) => write!(f, concat!("{:w$", $ty, "}"), value, w = width),
+                (true, false) => write!(f, concat!("{:#w$", $ty, "}"), value, w = width),
+                (false, true) => write!(f, concat!("{:0w$", $ty, "}"), value, w = width),
+                (true, true) => write!(f, concat!("{:#0w$", $ty, "}"), value, w = width),
+            }
+        };
+    }
+
+    Some(match ty {
+        b"" => write_value!(""),
+        b"?" => write_value!("?"),
+        b"x" => write_value!("x"),
+        b"X" => write_value!("X"),
+        b"o" => write_value!("o"),
+        b"b" => write_value!("b"),
+        _ => return None,
+    })
+}
+
+/// Write `bytes`, which must be valid UTF-8, to `f`.
+fn write_bytes(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    // All callers split strings only at ASCII characters, which keeps them valid UTF-8.
+    f.write_str(core::str::from_utf8(bytes).unwrap_or_default())
+}
+
+/// Split `bytes` before the first byte for which `pred` returns `true`, or at the end of
+/// `bytes`, without a panic path even if debug assertions are enabled.
+fn split_before(bytes: &[u8], pred: impl Fn(u8) -> bool) -> (&[u8], &[u8]) {
+    let mut rest = bytes;
+    while let [b, tail @ ..] = rest {
+        if pred(*b) {
+            break;
+        }
+        rest = tail;
+    }
+    let head = bytes
+        .get(..bytes.len().saturating_sub(rest.len()))
+        .unwrap_or_default();
+    (head, rest)
+}
+
+/// Split `s` at every `'\n'`, like `s.split('\n')`, but without a panic path.
+fn split_lines(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(s);
+    core::iter::from_fn(move || {
+        let s = rest.take()?;
+        match split_line(s) {
+            Some((line, next)) => {
+                rest = Some(next);
+                Some(line)
+            }
+            None => Some(s),
+        }
+    })
+}
+
+/// Split `s` at the first `'\n'`, like `s.split_once('\n')`, but without a panic path
+/// even if debug assertions are enabled.
+pub(crate) fn split_line(s: &str) -> Option<(&str, &str)> {
+    match split_before(s.as_bytes(), |b| b == b'\n') {
+        // Splitting at an ASCII character keeps both parts valid UTF-8.
+        (line, [_, tail @ ..]) => Some((
+            core::str::from_utf8(line).ok()?,
+            core::str::from_utf8(tail).ok()?,
+        )),
+        _ => None,
+    }
+}
+
+/// Remove leading and trailing whitespace from `s`, like `s.trim()`, but without a panic
+/// path even if debug assertions are enabled.
+fn trim_whitespace(s: &str) -> &str {
+    let mut bytes = s.as_bytes();
+    // Match the UTF-8 encoding of every character for which `char::is_whitespace()`
+    // returns `true`.
+    loop {
+        bytes = match bytes {
+            [0x09..=0x0d | 0x20, rest @ ..]
+            | [0xc2, 0x85 | 0xa0, rest @ ..]
+            | [0xe1, 0x9a, 0x80, rest @ ..]
+            | [0xe2, 0x80, 0x80..=0x8a | 0xa8 | 0xa9 | 0xaf, rest @ ..]
+            | [0xe2, 0x81, 0x9f, rest @ ..]
+            | [0xe3, 0x80, 0x80, rest @ ..] => rest,
+            _ => break,
+        };
+    }
+    loop {
+        bytes = match bytes {
+            [rest @ .., 0x09..=0x0d | 0x20]
+            | [rest @ .., 0xc2, 0x85 | 0xa0]
+            | [rest @ .., 0xe1, 0x9a, 0x80]
+            | [rest @ .., 0xe2, 0x80, 0x80..=0x8a | 0xa8 | 0xa9 | 0xaf]
+            | [rest @ .., 0xe2, 0x81, 0x9f]
+            | [rest @ .., 0xe3, 0x80, 0x80] => rest,
+            _ => break,
+        };
+    }
+    // Removing whole characters keeps `bytes` valid UTF-8.
+    core::str::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Query if any entry yielded by `iter` is the error code `error_code`.
+pub fn chain_has_code<C: crate::ErrorCategory>(
+    mut iter: impl Iterator<Item = (crate::ErrorCode, crate::ErrorCategoryHandle)>,
+    error_code: C,
+) -> bool {
+    let error_code: crate::ErrorCode = error_code.into();
+    iter.any(|(ec, handle)| ec == error_code && handle.is_handle_of::<C>())
+}
+
+/// Query if any entry yielded by `iter` belongs to the error category `C`.
+pub fn chain_has_category<C: crate::ErrorCategory>(
+    mut iter: impl Iterator<Item = (crate::ErrorCode, crate::ErrorCategoryHandle)>,
+) -> bool {
+    iter.any(|(_, handle)| handle.is_handle_of::<C>())
+}
+
+/// Query if the first entry yielded by `iter` is the error code `error_code`.
+pub fn top_is_code<C: crate::ErrorCategory>(
+    iter: impl Iterator<Item = (crate::ErrorCode, crate::ErrorCategoryHandle)>,
+    error_code: C,
+) -> bool {
+    chain_has_code(iter.take(1), error_code)
+}
+
+/// Query if the first entry yielded by `iter` belongs to the error category `C`.
+pub fn top_is_category<C: crate::ErrorCategory>(
+    iter: impl Iterator<Item = (crate::ErrorCode, crate::ErrorCategoryHandle)>,
+) -> bool {
+    chain_has_category::<C>(iter.take(1))
+}
+
+/// Implemented for `C` and for [`Unused`](crate::marker::Unused), so that a link of a
+/// category is compatible with the link `C` of another category at the same index.
+pub trait LinkCompatible<C, Tag> {}
+
+/// Tag of the [`LinkCompatible`] implementation for the same category.
+pub struct SameLink;
+/// Tag of the [`LinkCompatible`] implementation for an unused link.
+pub struct UnusedLink;
+
+impl<C: crate::ErrorCategory> LinkCompatible<C, SameLink> for C {}
+impl<C: crate::ErrorCategory> LinkCompatible<C, UnusedLink> for crate::marker::Unused {}
+
+/// Implemented only for [`Unused`](crate::marker::Unused).
+pub trait IsUnused {}
+
+impl IsUnused for crate::marker::Unused {}
+
+/// Fails to compile if the link `L` is not compatible with the link `C`.
+pub fn assert_link_compatible<L: LinkCompatible<C, Tag>, C, Tag>() {}
+
+/// Fails to compile if the link `L` is used.
+pub fn assert_link_unused<L: IsUnused>() {}
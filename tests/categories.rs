@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::categories::{
+    CommonError, FmtError, I2cError, ParseFloatError, ParseIntError, SpiError, TryFromIntError,
+};
+use embedded_error_chain::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError, I2cError, CommonError))]
+#[repr(u8)]
+enum ImuError {
+    /// init failed
+    InitFailed,
+}
+
+fn parse(s: &str) -> Result<u8, Error<ParseIntError>> {
+    Ok(s.parse::<u8>().map_err(ParseIntError::from)?)
+}
+
+#[test]
+fn core_errors() {
+    assert_eq!(FmtError::from(core::fmt::Error), FmtError::Error);
+    assert_eq!(parse("").unwrap_err().code(), ParseIntError::Empty);
+    assert_eq!(parse("x").unwrap_err().code(), ParseIntError::InvalidDigit);
+    assert_eq!(parse("256").unwrap_err().code(), ParseIntError::PosOverflow);
+    assert_eq!(parse("42"), Ok(42));
+    assert_eq!(
+        ParseFloatError::from("x".parse::<f32>().unwrap_err()),
+        ParseFloatError::Invalid
+    );
+    assert_eq!(
+        TryFromIntError::from(u8::try_from(300u16).unwrap_err()),
+        TryFromIntError::OutOfRange
+    );
+}
+
+#[test]
+fn chain() {
+    let err = I2cError::NoAcknowledgeAddress.chain(ImuError::InitFailed);
+    assert!(err.caused_by(I2cError::NoAcknowledgeAddress));
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- I2cError(3): no acknowledge of the address"
+    );
+
+    let err = CommonError::Timeout.chain(ImuError::InitFailed);
+    assert_eq!(
+        err.code_of_category::<CommonError>(),
+        Some(CommonError::Timeout)
+    );
+    assert_eq!(format!("{:?}", SpiError::ModeFault), "mode fault");
+}
@@ -24,9 +24,9 @@ impl From<ErrorCode> for TestError1 {
     }
 }
 
-impl Into<ErrorCode> for TestError1 {
-    fn into(self) -> ErrorCode {
-        self as ErrorCode
+impl From<TestError1> for ErrorCode {
+    fn from(val: TestError1) -> Self {
+        val as ErrorCode
     }
 }
 
@@ -55,9 +55,9 @@ impl From<ErrorCode> for TestError2 {
     }
 }
 
-impl Into<ErrorCode> for TestError2 {
-    fn into(self) -> ErrorCode {
-        self as ErrorCode
+impl From<TestError2> for ErrorCode {
+    fn from(val: TestError2) -> Self {
+        val as ErrorCode
     }
 }
 
@@ -87,9 +87,9 @@ impl From<ErrorCode> for TestError3 {
     }
 }
 
-impl Into<ErrorCode> for TestError3 {
-    fn into(self) -> ErrorCode {
-        self as ErrorCode
+impl From<TestError3> for ErrorCode {
+    fn from(val: TestError3) -> Self {
+        val as ErrorCode
     }
 }
 
@@ -171,6 +171,7 @@ fn chain2() {
 }
 
 #[test]
+#[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
 #[should_panic(expected = "chaining two errors overflowed; error chain is full")]
 fn chain3() {
     let _err = Error::new(TestError2::Err1)
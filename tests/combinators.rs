@@ -0,0 +1,94 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::ResultErrorExt;
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout,
+    /// overrun
+    Overrun,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum SensorError {
+    /// retryable
+    Retryable,
+    /// fatal
+    Fatal,
+}
+
+fn spi_read(error_code: Option<SpiError>) -> Result<u8, Error<SpiError>> {
+    match error_code {
+        Some(error_code) => Err(Error::new(error_code)),
+        None => Ok(42),
+    }
+}
+
+#[test]
+fn chain_err_with() {
+    let classify = |err: &Error<SpiError>| match err.code() {
+        SpiError::BusError => SensorError::Retryable,
+        _ => SensorError::Fatal,
+    };
+
+    let err = spi_read(Some(SpiError::BusError))
+        .chain_err_with(classify)
+        .unwrap_err();
+    assert_eq!(err.code(), SensorError::Retryable);
+    assert!(err.caused_by(SpiError::BusError));
+
+    let err = spi_read(Some(SpiError::Overrun))
+        .chain_err_with(classify)
+        .unwrap_err();
+    assert_eq!(err.code(), SensorError::Fatal);
+
+    let mut called = false;
+    let val = spi_read(None).chain_err_with(|_| {
+        called = true;
+        SensorError::Fatal
+    });
+    assert_eq!(val, Ok(42));
+    assert!(!called);
+}
+
+#[test]
+fn map_code() {
+    let err = spi_read(Some(SpiError::BusError))
+        .chain_err(SensorError::Retryable)
+        .map_code(|_| SensorError::Fatal)
+        .unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "SensorError(1): fatal\n- SpiError(0): bus error"
+    );
+}
+
+#[test]
+fn recover_and_inspect() {
+    let recover_timeout = |err: &Error<SpiError>| match err.code() {
+        SpiError::Timeout => Some(0),
+        _ => None,
+    };
+    assert_eq!(
+        spi_read(Some(SpiError::Timeout)).or_else_recover(recover_timeout),
+        Ok(0)
+    );
+    assert_eq!(
+        spi_read(Some(SpiError::Overrun))
+            .or_else_recover(recover_timeout)
+            .unwrap_err()
+            .code(),
+        SpiError::Overrun
+    );
+
+    let mut logged = None;
+    let res = spi_read(Some(SpiError::BusError)).inspect_chain(|err| logged = Some(err.code()));
+    assert_eq!(logged, Some(SpiError::BusError));
+    assert_eq!(res.unwrap_err().code(), SpiError::BusError);
+}
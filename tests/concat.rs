@@ -0,0 +1,123 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError, GyroAccError))]
+#[repr(u8)]
+enum ImuError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(ImuError))]
+#[repr(u8)]
+enum AppError {
+    /// startup failed
+    StartupFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(AppError), wide)]
+#[repr(u8)]
+enum SystemError {
+    /// halted
+    Halted = 100,
+}
+
+#[test]
+fn concat() {
+    let mut err = ImuError::CalibrationFailed.chain(AppError::StartupFailed);
+    let source = SpiError::BusError.chain(GyroAccError::ReadoutFailed);
+    assert!(!err.concat(source));
+    assert_eq!(
+        err,
+        SpiError::BusError
+            .chain(GyroAccError::ReadoutFailed)
+            .chain(ImuError::CalibrationFailed)
+            .chain(AppError::StartupFailed)
+    );
+
+    // The context can also be a single error code.
+    let mut err = Error::new(ImuError::CalibrationFailed);
+    assert!(!err.concat(SpiError::BusError));
+    assert_eq!(err, SpiError::BusError.chain(ImuError::CalibrationFailed));
+
+    let mut err: DynError = Error::new(SystemError::Halted).into();
+    assert!(!err.concat(ImuError::CalibrationFailed.chain(AppError::StartupFailed)));
+    assert_eq!(
+        format!("{:?}", err),
+        "SystemError(100): halted\n- AppError(0): startup failed\n- ImuError(0): calibration failed"
+    );
+}
+
+#[test]
+fn try_concat() {
+    let mut err = ImuError::CalibrationFailed.chain(AppError::StartupFailed);
+    let before = err;
+    let source = err.try_concat(AppError::StartupFailed);
+    assert!(source.unwrap_err().is::<AppError>());
+    assert_eq!(err, before);
+
+    assert_eq!(err.try_concat(GyroAccError::ReadoutFailed), Ok(false));
+    assert_eq!(err.chain_len(), 2);
+}
+
+#[test]
+#[cfg(not(feature = "no-panic"))]
+#[should_panic(expected = "cannot concatenate unlinked error categories")]
+fn concat_unlinked() {
+    Error::new(GyroAccError::ReadoutFailed).concat(AppError::StartupFailed);
+}
+
+#[test]
+#[cfg(not(feature = "no-panic"))]
+#[should_panic(expected = "cannot concatenate a wide error code")]
+fn concat_wide() {
+    DynError::from(GyroAccError::ReadoutFailed).concat(SystemError::Halted);
+}
+
+#[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+#[test]
+#[should_panic(expected = "concatenating two errors overflowed; error chain is full")]
+fn concat_overflow() {
+    let mut err = Error::new(SystemError::Halted);
+    err.concat(
+        SpiError::BusError
+            .chain(GyroAccError::ReadoutFailed)
+            .chain(ImuError::CalibrationFailed)
+            .chain(AppError::StartupFailed),
+    );
+}
+
+#[cfg(not(feature = "panic-on-overflow"))]
+#[test]
+fn concat_overflow() {
+    let mut err = Error::new(SystemError::Halted);
+    let truncated = err.concat(
+        SpiError::BusError
+            .chain(GyroAccError::ReadoutFailed)
+            .chain(ImuError::CalibrationFailed)
+            .chain(AppError::StartupFailed),
+    );
+    assert!(truncated);
+    assert_eq!(
+        format!("{:?}", err),
+        "SystemError(100): halted\n- AppError(0): startup failed\n- ImuError(0): calibration failed\n- GyroAccError(0): readout failed"
+    );
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum ImuError {
+    /// init failed
+    InitFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(ImuError))]
+#[repr(u8)]
+enum DriverError {
+    /// start failed
+    StartFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(ImuError, DriverError))]
+#[repr(u8)]
+enum ControlTaskError {
+    /// init failed
+    InitFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide, links(ControlTaskError))]
+#[repr(u8)]
+enum SystemStatus {
+    /// halted
+    Halted = 0x20,
+}
+
+fn read_who_am_i() -> Result<u8, SpiError> {
+    Err(SpiError::BusError)
+}
+
+fn imu_init() -> Result<(), ContextError> {
+    read_who_am_i()
+        .context("reading WHO_AM_I")
+        .chain_err(ImuError::InitFailed)
+        .context("imu 0")?;
+    Ok(())
+}
+
+#[test]
+fn context() {
+    let err = ContextError::from(SpiError::BusError)
+        .context("reading WHO_AM_I")
+        .chain(ImuError::InitFailed)
+        .context("imu 0");
+    assert_eq!(
+        err.contexts(),
+        [Some("imu 0"), Some("reading WHO_AM_I"), None, None, None]
+    );
+    assert!(err.caused_by(SpiError::BusError));
+
+    let err = err.chain(ControlTaskError::InitFailed);
+    assert_eq!(err.contexts()[0], None);
+    assert_eq!(
+        format!("{:?}", err),
+        "ControlTaskError(0): init failed
+- ImuError(0): init failed (imu 0)
+- SpiError(0): bus error (reading WHO_AM_I)"
+    );
+
+    let err = DynError::from(SpiError::BusError)
+        .context("first")
+        .context("second");
+    assert_eq!(err.contexts()[0], Some("second"));
+}
+
+#[test]
+fn chain_err() {
+    // `chain_err()` returns a plain `Error`, so only the context attached afterwards is
+    // kept.
+    let err = imu_init().unwrap_err();
+    assert_eq!(err.contexts(), [Some("imu 0"), None, None, None, None]);
+    assert!(err.caused_by(SpiError::BusError));
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed (imu 0)\n- SpiError(0): bus error"
+    );
+}
+
+/// A context error with contexts on a full chain of narrow error codes.
+fn full_chain() -> ContextError {
+    ContextError::from(SpiError::BusError)
+        .context("spi")
+        .chain(ImuError::InitFailed)
+        .context("imu")
+        .chain(DriverError::StartFailed)
+        .context("driver")
+        .chain(ControlTaskError::InitFailed)
+        .context("control")
+}
+
+#[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+#[test]
+#[should_panic(expected = "chaining two errors overflowed; error chain is full")]
+fn wide_overflow() {
+    full_chain().chain(SystemStatus::Halted);
+}
+
+#[cfg(not(all(feature = "panic-on-overflow", not(feature = "no-panic"))))]
+#[test]
+fn wide_overflow() {
+    // The wide error code takes the slot of the oldest error, whose context is dropped
+    // with it.
+    let err = full_chain().chain(SystemStatus::Halted);
+    assert_eq!(err.error().chain_len(), 3);
+    assert_eq!(
+        err.contexts(),
+        [None, Some("control"), Some("driver"), Some("imu"), None]
+    );
+}
+
+#[test]
+fn size() {
+    use core::mem::size_of;
+
+    assert_eq!(size_of::<Error<SpiError>>(), 4);
+}
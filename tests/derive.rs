@@ -1,3 +1,4 @@
+#![cfg(feature = "derive")]
 #![allow(dead_code)]
 use embedded_error_chain::*;
 
@@ -46,6 +47,7 @@ enum SeperateError {
     SomethingHappened,
 }
 
+#[cfg(not(feature = "no-panic"))]
 #[derive(Clone, Copy, ErrorCategory)]
 enum YetEmptyError {}
 
@@ -1,3 +1,4 @@
+#![cfg(feature = "derive")]
 #![allow(dead_code)]
 use embedded_error_chain::*;
 
@@ -46,6 +47,7 @@ enum SeperateError {
     SomethingHappened,
 }
 
+#[cfg(not(feature = "no-panic"))]
 #[derive(Clone, Copy, ErrorCategory)]
 enum YetEmptyError {}
 
@@ -83,6 +85,7 @@ fn test_iter() {
 }
 
 #[test]
+#[cfg(not(feature = "no-panic"))]
 #[should_panic(expected = "cannot chain unlinked error categories: optional name(2): Summary")]
 fn test_chain_panic() {
     let err: DynError = TestError::Bar.into();
@@ -0,0 +1,79 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::ChainEntry;
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// init failed
+    InitFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(GyroAccError))]
+#[repr(u8)]
+enum ImuError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+#[test]
+fn entries() {
+    let err = SpiError::Timeout
+        .chain(GyroAccError::InitFailed)
+        .chain(ImuError::CalibrationFailed);
+
+    let entries: Vec<ChainEntry> = err.entries().collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        entries.iter().map(ChainEntry::position).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+
+    assert!(entries[0].is::<ImuError>());
+    assert_eq!(
+        entries[0].downcast::<ImuError>(),
+        Some(ImuError::CalibrationFailed)
+    );
+    assert_eq!(entries[0].downcast::<SpiError>(), None);
+    assert_eq!(entries[1].category().name(), "GyroAccError");
+    assert_eq!(entries[2].code(), 1);
+    assert_eq!(format!("{:?}", entries[2]), "SpiError(1): timeout");
+    assert_eq!(entries[1].to_string(), "GyroAccError(0): init failed");
+
+    let dyn_err: DynError = err.into();
+    assert_eq!(
+        dyn_err.entries().map(|e| e.code()).collect::<Vec<_>>(),
+        err.entries().map(|e| e.code()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn root_cause() {
+    let err = SpiError::BusError
+        .chain(GyroAccError::InitFailed)
+        .chain(ImuError::CalibrationFailed);
+    let root = err.root_cause();
+    assert_eq!(root.downcast::<SpiError>(), Some(SpiError::BusError));
+    assert_eq!(root.position(), 2);
+
+    let dyn_err: DynError = err.into();
+    assert_eq!(
+        dyn_err.root_cause().downcast::<SpiError>(),
+        Some(SpiError::BusError)
+    );
+
+    let err = Error::new(SpiError::Timeout);
+    assert_eq!(err.root_cause().downcast(), Some(SpiError::Timeout));
+    assert_eq!(err.root_cause().position(), 0);
+}
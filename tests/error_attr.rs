@@ -1,3 +1,5 @@
+#![cfg(feature = "derive")]
+#![allow(dead_code)]
 use embedded_error_chain::prelude::*;
 
 #[derive(Clone, Copy, ErrorCategory)]
@@ -37,6 +39,7 @@ enum SeperateError {
     SomethingHappened,
 }
 
+#[cfg(not(feature = "no-panic"))]
 #[derive(Clone, Copy, ErrorCategory)]
 enum YetEmptyError {}
 
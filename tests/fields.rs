@@ -0,0 +1,84 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+enum SensorError {
+    /// not connected
+    NotConnected,
+    #[error("timeout after {0} ms")]
+    Timeout(u16),
+    /// Invalid readout
+    InvalidReadout(u16),
+    #[error("{variant}: {:#06x} ({summary})")]
+    /// register mismatch
+    Register(u16),
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SensorError))]
+#[repr(u8)]
+enum ImuError {
+    /// init failed
+    InitFailed,
+}
+
+#[test]
+fn codes() {
+    assert_eq!(ErrorCode::from(SensorError::NotConnected), 0);
+    assert_eq!(ErrorCode::from(SensorError::Timeout(100)), 1);
+    assert_eq!(ErrorCode::from(SensorError::Register(0x75)), 3);
+
+    assert!(matches!(SensorError::from(1), SensorError::Timeout(0)));
+    assert!(matches!(
+        SensorError::from_payload(1, 100),
+        SensorError::Timeout(100)
+    ));
+    assert!(matches!(
+        SensorError::from_payload(0, 100),
+        SensorError::NotConnected
+    ));
+    assert_eq!(SensorError::Timeout(100).payload(), 100);
+    assert_eq!(SensorError::NotConnected.payload(), 0);
+}
+
+#[test]
+fn debug() {
+    assert_eq!(
+        format!("{:?}", SensorError::Timeout(100)),
+        "timeout after 100 ms"
+    );
+    assert_eq!(
+        format!("{:?}", SensorError::Register(0x75)),
+        "Register: 0x0075 (register mismatch)"
+    );
+    assert_eq!(
+        format!("{:?}", SensorError::InvalidReadout(3)),
+        "Invalid readout"
+    );
+}
+
+#[test]
+fn error() {
+    let err = Error::from_variant(SensorError::Timeout(250));
+    assert_eq!(err.payload(), 250);
+    assert!(matches!(err.variant(), SensorError::Timeout(250)));
+    assert_eq!(format!("{:?}", err), "SensorError(1): timeout after 250 ms");
+
+    let err = err.chain(ImuError::InitFailed);
+    assert!(err.caused_by(SensorError::Timeout(0)));
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- SensorError(1): Timeout"
+    );
+
+    let err = Error::from_variant(SensorError::Register(0x75)).chain(ImuError::InitFailed);
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- SensorError(3): register mismatch"
+    );
+    let err = Error::from_variant(SensorError::InvalidReadout(3)).chain(ImuError::InitFailed);
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- SensorError(2): Invalid readout"
+    );
+}
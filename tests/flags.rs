@@ -0,0 +1,151 @@
+#![cfg(feature = "derive")]
+#![allow(dead_code)]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum DriverError {
+    Fault,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ErrorCategory)]
+#[error_category(links(DriverError))]
+#[repr(u8)]
+enum MotorFault {
+    /// over current
+    OverCurrent,
+    /// over temperature
+    OverTemperature,
+    UnderVoltage = 15,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide)]
+#[repr(u8)]
+enum StatusFault {
+    Busy = 0x20,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ErrorCategory)]
+#[repr(u8)]
+enum FanFault {
+    Stalled,
+    #[error(unknown)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(MotorFault))]
+#[repr(u8)]
+enum ControlError {
+    Stopped,
+}
+
+#[test]
+fn set_clear() {
+    let mut flags = ErrorFlags::new();
+    assert!(flags.is_empty());
+    assert!(flags.set(MotorFault::UnderVoltage));
+    assert!(!flags.set(MotorFault::UnderVoltage));
+    assert!(flags.set(MotorFault::OverCurrent));
+    assert_eq!(flags.bits(), 0x8001);
+    assert_eq!(flags.len(), 2);
+
+    assert!(flags.clear(MotorFault::OverCurrent));
+    assert!(!flags.clear(MotorFault::OverCurrent));
+    assert!(!flags.contains(MotorFault::OverCurrent));
+    assert!(flags.contains(MotorFault::UnderVoltage));
+
+    let mut iter = flags.iter();
+    assert_eq!(iter.next(), Some(MotorFault::UnderVoltage));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn set_ops() {
+    let a: ErrorFlags<MotorFault> = [MotorFault::OverCurrent, MotorFault::OverTemperature]
+        .iter()
+        .copied()
+        .collect();
+    let b = ErrorFlags::from(MotorFault::OverTemperature) | MotorFault::UnderVoltage.into();
+
+    assert_eq!((a | b).len(), 3);
+    assert_eq!(a & b, ErrorFlags::from(MotorFault::OverTemperature));
+    assert_eq!(a - b, ErrorFlags::from(MotorFault::OverCurrent));
+    assert_eq!(
+        (a ^ b).iter().collect::<Vec<_>>(),
+        vec![MotorFault::OverCurrent, MotorFault::UnderVoltage]
+    );
+    assert!((a & b).is_subset(&a));
+
+    let mut c = a;
+    c -= b;
+    c |= ErrorFlags::from(MotorFault::UnderVoltage);
+    assert_eq!(c.bits(), 0x8001);
+}
+
+#[test]
+fn from_chain() {
+    let err = Error::new(DriverError::Fault)
+        .chain(MotorFault::OverTemperature)
+        .chain(ControlError::Stopped);
+
+    let flags: ErrorFlags<MotorFault> = err.into();
+    assert_eq!(flags, ErrorFlags::from(MotorFault::OverTemperature));
+
+    let flags: ErrorFlags<DriverError> = err.into();
+    assert_eq!(flags.len(), 1);
+}
+
+#[test]
+fn debug() {
+    let flags = ErrorFlags::from(MotorFault::OverCurrent) | MotorFault::UnderVoltage.into();
+    assert_eq!(
+        format!("{:?}", flags),
+        "MotorFault{over current, UnderVoltage}"
+    );
+}
+
+#[test]
+fn atomic() {
+    static FAULTS: AtomicErrorFlags<MotorFault> = AtomicErrorFlags::new();
+
+    assert!(FAULTS.set(MotorFault::OverCurrent));
+    assert!(!FAULTS.set(MotorFault::OverCurrent));
+    FAULTS.set(MotorFault::UnderVoltage);
+    assert!(FAULTS.clear(MotorFault::UnderVoltage));
+    assert!(FAULTS.contains(MotorFault::OverCurrent));
+
+    let flags = FAULTS.take();
+    assert_eq!(flags, ErrorFlags::from(MotorFault::OverCurrent));
+    assert!(FAULTS.load().is_empty());
+}
+
+#[test]
+fn wide_code() {
+    let mut flags = ErrorFlags::new();
+    assert!(!flags.set(StatusFault::Busy));
+    assert!(flags.is_empty());
+
+    assert!(!flags.contains(StatusFault::Busy));
+    assert!(ErrorFlags::from(StatusFault::Busy).is_empty());
+    assert!(ErrorFlags::<StatusFault>::from(Error::new(StatusFault::Busy)).is_empty());
+
+    let atomic = AtomicErrorFlags::new();
+    assert!(!atomic.set(StatusFault::Busy));
+    assert!(atomic.load().is_empty());
+}
+
+#[test]
+fn from_bits_invalid_code() {
+    let flags = ErrorFlags::<FanFault>::from_bits(0b1001);
+    assert_eq!(flags.bits(), 0b1001);
+    assert_eq!(flags.len(), 2);
+    assert!(flags.contains(FanFault::Stalled));
+    assert_eq!(
+        flags.iter().collect::<Vec<_>>(),
+        [FanFault::Stalled, FanFault::Unknown]
+    );
+    assert_eq!(format!("{:?}", flags), "FanFault{Stalled, Unknown}");
+    assert!(flags.is_subset(&ErrorFlags::from_bits(0b1011)));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::FutureChainError;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(GyroAccError))]
+#[repr(u8)]
+enum ImuError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// A future that is pending once before completing.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(val) = future.as_mut().poll(&mut cx) {
+            return val;
+        }
+    }
+}
+
+async fn spi_read(fail: bool) -> Result<u8, SpiError> {
+    YieldOnce(false).await;
+    if fail {
+        Err(SpiError::BusError)
+    } else {
+        Ok(42)
+    }
+}
+
+async fn gyro_acc_readout(fail: bool) -> Result<u8, Error<GyroAccError>> {
+    spi_read(fail).chain_err(GyroAccError::ReadoutFailed).await
+}
+
+async fn imu_calibrate() -> Result<(), Error<ImuError>> {
+    gyro_acc_readout(true)
+        .chain_err(ImuError::CalibrationFailed)
+        .await?;
+    Ok(())
+}
+
+async fn dyn_readout() -> Result<u8, DynError> {
+    Ok(gyro_acc_readout(true).await?)
+}
+
+#[test]
+fn chain_err() {
+    assert_eq!(block_on(gyro_acc_readout(false)).unwrap(), 42);
+
+    let err = block_on(imu_calibrate()).unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): calibration failed\n- GyroAccError(0): readout failed\n- SpiError(0): bus error"
+    );
+
+    let err = block_on(dyn_readout().chain_err(ImuError::CalibrationFailed)).unwrap_err();
+    assert!(err.caused_by(SpiError::BusError));
+    assert!(err.caused_by(GyroAccError::ReadoutFailed));
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "derive")]
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use embedded_error_chain::*;
+
+trait Bus: Copy {
+    type Error: ErrorCategory;
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum I2cError {
+    /// nack
+    Nack = 3,
+}
+
+#[derive(Clone, Copy)]
+struct Spi;
+
+impl Bus for Spi {
+    type Error = SpiError;
+}
+
+#[derive(Clone, Copy)]
+struct I2c;
+
+impl Bus for I2c {
+    type Error = I2cError;
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(name = "DeviceError", links(B::Error))]
+enum DeviceError<B: Bus> {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+    #[doc(hidden)]
+    _Marker(Infallible, PhantomData<B>),
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u16)]
+enum StatusError {
+    /// ok
+    Ok,
+    /// busy
+    Busy = 7,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide)]
+#[repr(u16)]
+enum ChannelError<const N: usize> {
+    /// overrun
+    Overrun = 20,
+    /// underrun
+    Underrun = 200,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+enum PlainError {
+    /// first
+    First,
+    /// second
+    Second = 12,
+}
+
+#[test]
+fn generic() {
+    assert_eq!(ErrorCode::from(DeviceError::<Spi>::ReadoutFailed), 1);
+    assert!(matches!(
+        DeviceError::<I2c>::from(0),
+        DeviceError::InitFailed
+    ));
+
+    let err = SpiError::Timeout.chain(DeviceError::<Spi>::ReadoutFailed);
+    assert!(err.caused_by(SpiError::Timeout));
+    assert_eq!(
+        format!("{:?}", err),
+        "DeviceError(1): readout failed\n- SpiError(1): timeout"
+    );
+
+    let err = I2cError::Nack.chain(DeviceError::<I2c>::InitFailed);
+    assert!(matches!(
+        err.code_of_category::<I2cError>(),
+        Some(I2cError::Nack)
+    ));
+    assert!(!DynError::from(err).is::<DeviceError<Spi>>());
+}
+
+#[test]
+fn repr() {
+    let code: ErrorCode = StatusError::Busy.into();
+    assert_eq!(code, 7);
+    assert!(matches!(StatusError::from(7), StatusError::Busy));
+    assert_eq!(
+        format!("{:?}", Error::new(StatusError::Busy)),
+        "StatusError(7): busy"
+    );
+
+    let code: ErrorCode = PlainError::Second.into();
+    assert_eq!(code, 12);
+    assert!(matches!(PlainError::from(0), PlainError::First));
+    assert!(matches!(PlainError::from(12), PlainError::Second));
+}
+
+#[test]
+fn const_generic() {
+    let code: ErrorCode = ChannelError::<2>::Underrun.into();
+    assert_eq!(code, 200);
+    assert!(matches!(ChannelError::<2>::from(20), ChannelError::Overrun));
+    assert_eq!(
+        format!("{:?}", Error::new(ChannelError::<1>::Overrun)),
+        "ChannelError(20): overrun"
+    );
+}
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "derive", feature = "embedded-hal"))]
+
+use embedded_error_chain::categories::{DigitalError, I2cError, SpiError};
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::{ResultChainKind, ResultKind};
+use embedded_hal::{digital, i2c, spi};
+
+/// An error of a driver standing in for a HAL crate.
+#[derive(Debug)]
+enum HalI2cError {
+    Nack,
+    Dma,
+}
+
+impl i2c::Error for HalI2cError {
+    fn kind(&self) -> i2c::ErrorKind {
+        match self {
+            HalI2cError::Nack => i2c::ErrorKind::NoAcknowledge(i2c::NoAcknowledgeSource::Address),
+            HalI2cError::Dma => i2c::ErrorKind::Other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError, I2cError, DigitalError))]
+#[repr(u8)]
+enum ImuError {
+    /// init failed
+    InitFailed,
+}
+
+fn i2c_write(err: HalI2cError) -> Result<(), HalI2cError> {
+    Err(err)
+}
+
+#[test]
+fn kinds() {
+    assert_eq!(SpiError::from(spi::ErrorKind::Overrun), SpiError::Overrun);
+    assert_eq!(
+        SpiError::from(spi::ErrorKind::ChipSelectFault),
+        SpiError::ChipSelectFault
+    );
+    assert_eq!(SpiError::from(spi::ErrorKind::Other), SpiError::Other);
+    assert_eq!(
+        I2cError::from(i2c::ErrorKind::NoAcknowledge(
+            i2c::NoAcknowledgeSource::Data
+        )),
+        I2cError::NoAcknowledgeData
+    );
+    assert_eq!(
+        I2cError::from(i2c::ErrorKind::NoAcknowledge(
+            i2c::NoAcknowledgeSource::Unknown
+        )),
+        I2cError::NoAcknowledge
+    );
+    assert_eq!(
+        I2cError::from(i2c::ErrorKind::ArbitrationLoss),
+        I2cError::ArbitrationLoss
+    );
+    assert_eq!(
+        DigitalError::from(digital::ErrorKind::Other),
+        DigitalError::Other
+    );
+}
+
+#[test]
+fn chain_kind() {
+    let err = i2c_write(HalI2cError::Nack)
+        .chain_kind(ImuError::InitFailed)
+        .unwrap_err();
+    assert!(err.caused_by(I2cError::NoAcknowledgeAddress));
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- I2cError(3): no acknowledge of the address"
+    );
+
+    let err = i2c_write(HalI2cError::Dma).kind_err().unwrap_err();
+    assert_eq!(err.code(), I2cError::Other);
+
+    let err = Err::<(), _>(spi::ErrorKind::ModeFault)
+        .chain_kind(ImuError::InitFailed)
+        .unwrap_err();
+    assert!(err.caused_by(SpiError::ModeFault));
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(GyroAccError, SensorError))]
+#[repr(u8)]
+enum SensorError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide, links(SensorError))]
+#[repr(u8)]
+enum SystemStatus {
+    /// halted
+    Halted = 0x20,
+}
+
+fn spi_init() -> Result<(), LocatedError<SpiError>> {
+    Err(LocatedError::new(SpiError::BusError))
+}
+
+#[test]
+fn chain() {
+    let spi_line = line!() + 1;
+    let spi_read: Result<u8, _> = Err(LocatedError::new(SpiError::BusError));
+    let line = line!() + 1;
+    let err = spi_read.map_err(|err| err.chain(GyroAccError::ReadoutFailed));
+    let err = err.unwrap_err();
+
+    assert!(matches!(err.code(), GyroAccError::ReadoutFailed));
+    assert_eq!(err.error().chain_len(), 1);
+
+    let locations = err.locations();
+    if cfg!(feature = "location") {
+        assert_eq!(locations[0].unwrap().line(), line);
+        assert_eq!(locations[0].unwrap().file(), file!());
+        assert_eq!(locations[1].unwrap().line(), spi_line);
+        assert!(locations[2].is_none());
+    } else {
+        assert!(locations.iter().all(Option::is_none));
+    }
+}
+
+#[test]
+fn chain_err() {
+    let line = line!() + 2;
+    let gyro_acc_init = || -> Result<(), LocatedError<GyroAccError>> {
+        spi_init().chain_err(GyroAccError::InitFailed)?;
+        Ok(())
+    };
+    let err = gyro_acc_init().unwrap_err();
+    assert!(matches!(err.code(), GyroAccError::InitFailed));
+    assert!(err.error().caused_by(SpiError::BusError));
+
+    // Only the location of `?` is recorded, the location of the chained error is lost.
+    let locations = err.locations();
+    if cfg!(feature = "location") {
+        assert_eq!(locations[0].unwrap().line(), line);
+        assert!(locations[1].is_none());
+    } else {
+        assert!(locations.iter().all(Option::is_none));
+    }
+}
+
+#[test]
+fn debug() {
+    let line = line!() + 1;
+    let err = LocatedError::new(SpiError::BusError).chain(GyroAccError::ReadoutFailed);
+
+    let expected = if cfg!(feature = "location") {
+        format!(
+            "GyroAccError(1): readout failed at {file}:{line}\n- SpiError(0): bus error at {file}:{line}",
+            file = file!(),
+            line = line
+        )
+    } else {
+        "GyroAccError(1): readout failed\n- SpiError(0): bus error".into()
+    };
+    assert_eq!(format!("{:?}", err), expected);
+}
+
+#[cfg(not(all(feature = "panic-on-overflow", not(feature = "no-panic"))))]
+#[test]
+fn wide_overflow() {
+    let line = line!() + 1;
+    let err = LocatedError::new(SpiError::BusError)
+        .chain(GyroAccError::InitFailed)
+        .chain(SensorError::CalibrationFailed)
+        .chain(SensorError::CalibrationFailed)
+        .chain(SystemStatus::Halted);
+
+    // The wide error code takes the slot of the oldest error, whose location is dropped
+    // with it.
+    assert_eq!(err.error().chain_len(), 3);
+    let locations = err.locations();
+    if cfg!(feature = "location") {
+        let lines: Vec<_> = locations[..4].iter().map(|l| l.unwrap().line()).collect();
+        assert_eq!(lines, [line + 4, line + 3, line + 2, line + 1]);
+        assert!(locations[4].is_none());
+    } else {
+        assert!(locations.iter().all(Option::is_none));
+    }
+}
+
+#[test]
+fn size() {
+    use core::mem::size_of;
+
+    if !cfg!(feature = "location") {
+        assert_eq!(
+            size_of::<LocatedError<SpiError>>(),
+            size_of::<Error<SpiError>>()
+        );
+    }
+}
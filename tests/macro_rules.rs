@@ -0,0 +1,163 @@
+use embedded_error_chain::{error_category, prelude::*, ErrorCode, ErrorData};
+
+error_category! {
+    #[derive(Clone, Copy)]
+    #[repr(u8)]
+    enum SpiError {
+        /// bus error
+        BusError,
+        /// timeout
+        ///
+        /// details
+        Timeout = 5,
+        Overrun,
+    }
+}
+
+static LAST_READOUT: usize = 200;
+
+error_category! {
+    /// Gyroscope errors.
+    #[error_category(links(SpiError), name = "GyroAcc")]
+    #[derive(Clone, Copy)]
+    enum GyroAccError {
+        /// init failed
+        InitFailed,
+        /// ignored summary
+        #[error("{} (readout={})", "ReadoutFailed", LAST_READOUT)]
+        ReadoutFailed,
+    }
+}
+
+error_category! {
+    #[error_category(wide, links(GyroAccError, SpiError))]
+    #[derive(Clone, Copy)]
+    #[repr(u8)]
+    pub(crate) enum HalError {
+        /// busy
+        Busy = 17,
+    }
+}
+
+#[cfg(not(feature = "no-panic"))]
+error_category! {
+    #[derive(Clone, Copy)]
+    enum EmptyError {}
+}
+
+#[test]
+fn codes() {
+    let code: ErrorCode = SpiError::Overrun.into();
+    assert_eq!(code, 6);
+    assert!(matches!(SpiError::from(5), SpiError::Timeout));
+    assert_eq!(<HalError as ErrorCategory>::NAME, "HalError");
+    assert_eq!(<GyroAccError as ErrorCategory>::NAME, "GyroAcc");
+    assert!(ErrorData::from(Error::new(HalError::Busy)).is_wide());
+    assert!(!ErrorData::from(Error::new(SpiError::BusError)).is_wide());
+}
+
+#[test]
+fn debug() {
+    assert_eq!(format!("{:?}", SpiError::BusError), "bus error");
+    assert_eq!(format!("{:?}", SpiError::Timeout), "timeout");
+    assert_eq!(format!("{:?}", SpiError::Overrun), "Overrun");
+    assert_eq!(
+        format!("{:?}", GyroAccError::ReadoutFailed),
+        "ReadoutFailed (readout=200)"
+    );
+}
+
+#[test]
+fn chain() {
+    let err = SpiError::Timeout
+        .chain(GyroAccError::InitFailed)
+        .chain(HalError::Busy);
+    assert!(err.caused_by(SpiError::Timeout));
+    assert_eq!(
+        format!("{:?}", err),
+        "HalError(17): busy\n- GyroAcc(0): init failed\n- SpiError(5): timeout"
+    );
+}
+
+/// Declare the same category with the derive macro and with `error_category!`.
+#[cfg(feature = "derive")]
+macro_rules! doc_summaries {
+    ($derived:ident, $declared:ident) => {
+        #[derive(Clone, Copy, embedded_error_chain::ErrorCategory)]
+        #[error_category(name = "Summaries")]
+        #[repr(u8)]
+        enum $derived {
+            /// uses {{braces}} and }}
+            Braces,
+            /// spans
+            ///   multiple lines
+            ///
+            /// details
+            MultiLine,
+            /// {category}::{variant} at {value:#04x}, {value:5X}
+            Placeholders,
+            #[doc = "\u{a0}padded\u{3000}"]
+            Padded,
+        }
+
+        error_category! {
+            #[error_category(name = "Summaries")]
+            #[derive(Clone, Copy)]
+            #[repr(u8)]
+            enum $declared {
+                /// uses {{braces}} and }}
+                Braces,
+                /// spans
+                ///   multiple lines
+                ///
+                /// details
+                MultiLine,
+                /// {category}::{variant} at {value:#04x}, {value:5X}
+                Placeholders,
+                #[doc = "\u{a0}padded\u{3000}"]
+                Padded,
+            }
+        }
+    };
+}
+
+#[cfg(feature = "derive")]
+doc_summaries!(DerivedSummaries, DeclaredSummaries);
+
+#[cfg(feature = "derive")]
+#[test]
+fn doc_summary_like_derive() {
+    let pairs = [
+        (DerivedSummaries::Braces, DeclaredSummaries::Braces),
+        (DerivedSummaries::MultiLine, DeclaredSummaries::MultiLine),
+        (
+            DerivedSummaries::Placeholders,
+            DeclaredSummaries::Placeholders,
+        ),
+        (DerivedSummaries::Padded, DeclaredSummaries::Padded),
+    ];
+    for (derived, declared) in pairs {
+        assert_eq!(format!("{:?}", derived), format!("{:?}", declared));
+        assert_eq!(
+            format!("{:?}", Error::with_payload(derived, 0x75u8)),
+            format!("{:?}", Error::with_payload(declared, 0x75u8))
+        );
+    }
+
+    assert_eq!(
+        format!("{:?}", DeclaredSummaries::Braces),
+        "uses {braces} and }"
+    );
+    assert_eq!(
+        format!("{:?}", DeclaredSummaries::MultiLine),
+        "spans multiple lines"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            Error::with_payload(DeclaredSummaries::Placeholders, 0x75u8)
+        ),
+        "Summaries(2): Summaries::Placeholders at 0x75,    75"
+    );
+    assert_eq!(format!("{:?}", DeclaredSummaries::Padded), "padded");
+}
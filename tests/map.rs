@@ -0,0 +1,96 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::{FromCategory, ResultErrorExt};
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum SubError {
+    /// timeout
+    Timeout,
+    /// busy
+    Busy,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum OtherError {
+    /// first
+    First,
+    /// second
+    Second,
+    /// third
+    Third,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum ModuleError {
+    /// not ready
+    #[error(map(SubError::Timeout, SubError::Busy))]
+    NotReady,
+    #[error(map(OtherError::First))]
+    #[error("invalid config")]
+    InvalidConfig = 3,
+    /// unknown
+    #[error(unknown)]
+    Unknown,
+}
+
+fn sub_init() -> Result<(), Error<SubError>> {
+    Err(SpiError::BusError.chain(SubError::Timeout))
+}
+
+#[test]
+fn from_category() {
+    assert_eq!(
+        ModuleError::from_category(SubError::Busy),
+        ModuleError::NotReady
+    );
+    assert_eq!(
+        ModuleError::from_category(OtherError::First),
+        ModuleError::InvalidConfig
+    );
+    assert_eq!(
+        ModuleError::from_category(OtherError::Second),
+        ModuleError::Unknown
+    );
+    assert_eq!(
+        ModuleError::from_category(OtherError::Third),
+        ModuleError::Unknown
+    );
+}
+
+#[test]
+fn map_category() {
+    let err: Error<ModuleError> = sub_init().unwrap_err().map_category();
+    assert_eq!(err.code(), ModuleError::NotReady);
+    assert_eq!(err.chain_len(), 1);
+    assert_eq!(
+        format!("{:?}", err),
+        "ModuleError(0): not ready\n- SpiError(0): bus error"
+    );
+
+    let err: Error<ModuleError> = Error::new(OtherError::First).map_category();
+    assert_eq!(err.code(), ModuleError::InvalidConfig);
+    assert_eq!(format!("{:?}", err), "ModuleError(3): invalid config");
+}
+
+#[test]
+fn result_map_category() {
+    let res: Result<(), Error<ModuleError>> = sub_init().map_category();
+    let err = res.unwrap_err();
+    assert_eq!(err.code(), ModuleError::NotReady);
+    assert!(err.caused_by(SpiError::BusError));
+
+    let res: Result<u8, Error<ModuleError>> = Ok::<u8, Error<SubError>>(1).map_category();
+    assert_eq!(res.ok(), Some(1));
+}
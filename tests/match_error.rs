@@ -0,0 +1,137 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::categories::{self, CommonError};
+use embedded_error_chain::{match_error, prelude::*};
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(categories::SpiError, CommonError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(GyroAccError))]
+#[repr(u8)]
+enum ImuError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+fn classify(err: &DynError) -> u32 {
+    match_error!(err, {
+        top: GyroAccError::_ => 1,
+        categories::SpiError::ModeFault => 2,
+        categories::SpiError::_ => 3,
+        CommonError::Timeout => 4,
+        _ => 0,
+    })
+}
+
+#[test]
+fn arms() {
+    let err: DynError = categories::SpiError::Overrun
+        .chain(GyroAccError::InitFailed)
+        .into();
+    assert_eq!(classify(&err), 1);
+
+    let err: DynError = categories::SpiError::ModeFault
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed)
+        .into();
+    assert_eq!(classify(&err), 2);
+
+    let err: DynError = categories::SpiError::Overrun
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed)
+        .into();
+    assert_eq!(classify(&err), 3);
+
+    let err: DynError = CommonError::Timeout
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed)
+        .into();
+    assert_eq!(classify(&err), 4);
+
+    let err: DynError = Error::new(ImuError::CalibrationFailed).into();
+    assert_eq!(classify(&err), 0);
+}
+
+#[test]
+fn top() {
+    let err = CommonError::Busy.chain(GyroAccError::InitFailed);
+    let matched = match_error!(err, {
+        top: CommonError::Busy => "busy",
+        top: GyroAccError::ReadoutFailed => "readout",
+        top: GyroAccError::InitFailed => "init",
+        _ => "other",
+    });
+    assert_eq!(matched, "init");
+
+    // The error is only borrowed.
+    assert_eq!(
+        err.code_of_category::<CommonError>(),
+        Some(CommonError::Busy)
+    );
+}
+
+#[test]
+fn or_patterns() {
+    let classify = |err: Error<ImuError>| {
+        match_error!(err, {
+            categories::SpiError::ModeFault | CommonError::Timeout => 1,
+            top: GyroAccError::_ | top: GyroAccError::InitFailed | CommonError::Busy => 2,
+            _ => 0,
+        })
+    };
+
+    let err = categories::SpiError::ModeFault
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed);
+    assert_eq!(classify(err), 1);
+
+    let err = CommonError::Timeout
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed);
+    assert_eq!(classify(err), 1);
+
+    let err = CommonError::Busy
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed);
+    assert_eq!(classify(err), 2);
+
+    let err = categories::SpiError::Overrun
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed);
+    assert_eq!(classify(err), 0);
+}
+
+#[test]
+fn block_arms() {
+    let err = CommonError::Busy.chain(GyroAccError::InitFailed);
+    let mut retries = 0;
+    let matched = match_error!(err, {
+        CommonError::Timeout => {
+            retries += 1;
+            "timeout"
+        }
+        top: GyroAccError::InitFailed => {
+            retries += 2;
+            "init"
+        },
+        _ => {
+            retries += 3;
+            "other"
+        }
+    });
+    assert_eq!(matched, "init");
+    assert_eq!(retries, 2);
+
+    let matched = match_error!(err, {
+        CommonError::Busy => { "busy" }
+        _ => "other"
+    });
+    assert_eq!(matched, "busy");
+}
@@ -0,0 +1,77 @@
+#![cfg(all(feature = "derive", feature = "nb"))]
+
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::{block_err, NbResultChainError};
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SerialError {
+    /// overrun
+    Overrun,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SerialError))]
+#[repr(u8)]
+enum ModemError {
+    /// read failed
+    ReadFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(ModemError))]
+#[repr(u8)]
+enum AppError {
+    /// no connection
+    NoConnection,
+}
+
+fn serial_read(polls: &mut u32) -> nb::Result<u8, SerialError> {
+    *polls += 1;
+    match *polls {
+        1 => Err(nb::Error::WouldBlock),
+        2 => Ok(7),
+        _ => Err(nb::Error::Other(SerialError::Overrun)),
+    }
+}
+
+fn modem_read(polls: &mut u32) -> nb::Result<u8, Error<ModemError>> {
+    serial_read(polls).chain_err_nb(ModemError::ReadFailed)
+}
+
+fn spi_read() -> Result<u8, SerialError> {
+    Err(SerialError::Overrun)
+}
+
+#[test]
+fn chain_err_nb() {
+    let mut polls = 0;
+    assert!(matches!(modem_read(&mut polls), Err(nb::Error::WouldBlock)));
+    assert!(matches!(modem_read(&mut polls), Ok(7)));
+    match modem_read(&mut polls).chain_err_nb(AppError::NoConnection) {
+        Err(nb::Error::Other(err)) => assert_eq!(
+            format!("{:?}", err),
+            "AppError(0): no connection\n- ModemError(0): read failed\n- SerialError(0): overrun"
+        ),
+        _ => panic!("expected an error"),
+    }
+
+    // `ResultChainError` still applies to blocking results.
+    let err = spi_read().chain_err(ModemError::ReadFailed).unwrap_err();
+    assert!(err.caused_by(SerialError::Overrun));
+}
+
+#[test]
+fn block() {
+    let mut polls = 0;
+    let val: Result<u8, Error<SerialError>> = block_err!(serial_read(&mut polls));
+    assert_eq!(val, Ok(7));
+    assert_eq!(polls, 2);
+
+    let err = block_err!(serial_read(&mut polls), ModemError::ReadFailed).unwrap_err();
+    assert_eq!(err.code(), ModemError::ReadFailed);
+    assert!(err.caused_by(SerialError::Overrun));
+
+    let err = block_err!(modem_read(&mut polls), AppError::NoConnection).unwrap_err();
+    assert!(err.caused_by(ModemError::ReadFailed));
+}
@@ -0,0 +1,266 @@
+//! A `no_std` binary whose panic handler references an undefined symbol, so that linking
+//! fails if any panic path of the library is left with the feature `no-panic` enabled.
+//!
+//! It is built by `tests/no_panic.rs`. The library is optimized so that checks the compiler
+//! can prove to never fail are removed, and debug assertions and overflow checks are
+//! enabled so that they are checked as well. The inputs are derived from the number of
+//! command line arguments, so that they are unknown at compile time.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::{self, Write};
+use core::future::Future;
+use core::panic::PanicInfo;
+use core::pin::{pin, Pin};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use embedded_error_chain::prelude::*;
+use embedded_error_chain::{
+    block_err, categories, error_category, join, match_error, AtomicErrorFlags, ErrorData,
+    FutureChainError, LocatedError, NbResultChainError, PayloadCategory, ResultChainKind,
+    ResultErrorExt, ResultKind, Unlinked,
+};
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum DriverError {
+    /// init failed
+    InitFailed,
+    #[error(unknown)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(DriverError))]
+#[repr(u8)]
+enum AppError {
+    /// aborted
+    #[error(map(SensorError::Timeout, SensorError::Failed))]
+    Aborted,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+enum SensorError {
+    #[error("timeout after {0} ms")]
+    Timeout(u16),
+    /// failed
+    Failed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide)]
+#[repr(u8)]
+enum StatusError {
+    /// busy
+    Busy = 0x25,
+}
+
+error_category! {
+    #[error_category(links(SpiError, categories::SpiError))]
+    #[derive(Clone, Copy)]
+    #[repr(u8)]
+    enum BusError {
+        /// transfer failed
+        Transfer,
+        #[error("retried {} times", 3)]
+        Retried = 9,
+    }
+}
+
+static FAULTS: AtomicErrorFlags<DriverError> = AtomicErrorFlags::new();
+
+#[link(name = "c")]
+extern "C" {}
+
+/// Discards everything written to it.
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    extern "C" {
+        /// Intentionally not defined anywhere.
+        fn embedded_error_chain_panicked() -> !;
+    }
+    unsafe { embedded_error_chain_panicked() }
+}
+
+/// Referenced by the precompiled `core`, but never called with `panic = "abort"`.
+#[no_mangle]
+pub extern "C" fn rust_eh_personality() {}
+
+/// A future that is always ready with a copy of its result, unlike `core::future::Ready`
+/// which panics if polled again.
+struct Immediate(Result<(), SpiError>);
+
+impl Future for Immediate {
+    type Output = Result<(), SpiError>;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Ready(self.0)
+    }
+}
+
+/// A waker that does nothing, to poll futures without an executor.
+fn noop_waker() -> Waker {
+    fn raw() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    // SAFETY: The vtable functions don't use the data pointer.
+    unsafe { Waker::from_raw(raw()) }
+}
+
+#[no_mangle]
+pub extern "C" fn main(argc: isize, _argv: *const *const u8) -> isize {
+    let input = |i: u8| (argc as u8).wrapping_mul(i).wrapping_add(i);
+
+    // Invalid error codes.
+    let spi = SpiError::from(input(1));
+    let driver = DriverError::from(input(2));
+
+    // Chain overflows.
+    let mut err = spi.chain(driver).chain(AppError::Aborted);
+    for _ in 0..input(4) {
+        err = DynError::from(err).chain(AppError::Aborted);
+    }
+
+    // Unlinked categories.
+    let dyn_err = DynError::from(StatusError::Busy).chain(SpiError::Timeout);
+    let mut unlinked = DynError::from(err).chain(driver);
+    unlinked.concat(dyn_err);
+    unlinked.concat(StatusError::Busy);
+    let mut concat = Error::new(AppError::Aborted);
+    concat.concat(unlinked);
+
+    // Payloads and mapped categories.
+    let sensor = Error::from_variant(SensorError::from_payload(input(10), input(11).into()));
+    let _ = write!(Sink, "{:?}", sensor);
+    let mapped: Error<AppError> = sensor.without_payload().map_category();
+
+    // Other error types.
+    let tree: ErrorTree<3> = join(join(spi, driver), mapped).chain(AppError::Aborted);
+    let mut set = ErrorSet::<1>::new();
+    let _ = set.push(spi);
+    let _ = set.push(driver);
+    let context = ContextError::new(spi.into())
+        .context("spi")
+        .chain(driver)
+        .context("driver");
+    let located = LocatedError::new(spi).chain(driver);
+    let option = None::<u8>.ok_or_chain(located.error(), AppError::Aborted);
+    let _ = write!(Sink, "{:?} {:?} {:?} {:?}", tree, set, context, located);
+    let tree_matches = tree.caused_by(SpiError::Timeout) as isize
+        ^ tree.code_of_category::<DriverError>().is_some() as isize
+        ^ tree.root().is_some() as isize
+        ^ tree.children(input(12) as usize).next().is_some() as isize;
+    set.extend_from_results([Ok(()), Err(spi)]);
+    let set = set.check(|| Err::<(), _>(driver));
+    let set_first = set.iter().next().map_or(0, |err| err.code());
+    let _ = set.into_result();
+
+    // Error flags.
+    let mut flags = ErrorFlags::<SpiError>::from(Error::new(spi).chain(driver));
+    flags.set(SpiError::from(input(13)));
+    flags.clear(spi);
+    flags.extend([SpiError::BusError, SpiError::Timeout]);
+    FAULTS.set(driver);
+    FAULTS.clear(DriverError::from(input(14)));
+    let faults = FAULTS.take() | ErrorFlags::from(DriverError::InitFailed);
+    let _ = write!(Sink, "{:?} {:?}", flags, faults);
+
+    // Macro categories.
+    let macro_err = Error::new(SpiError::from(input(15))).chain(BusError::from(input(16)));
+    let _ = write!(Sink, "{:?}", macro_err);
+
+    // Non-blocking results.
+    let nb_err: nb::Result<(), SpiError> = match input(17) {
+        0 => Err(nb::Error::WouldBlock),
+        _ => Err(nb::Error::Other(spi)),
+    };
+    let nb_err = nb_err.chain_err_nb(DriverError::InitFailed);
+    let mut polls = 0u8;
+    let blocked = block_err!(
+        {
+            polls = polls.wrapping_add(1);
+            if polls < input(18) {
+                Err(nb::Error::WouldBlock)
+            } else {
+                Err(nb::Error::Other(spi))
+            }
+        },
+        DriverError::InitFailed
+    );
+    let blocked: Result<(), Error<DriverError>> = blocked;
+
+    // embedded-hal errors.
+    let spi_kind = match input(19) {
+        0 => embedded_hal::spi::ErrorKind::Overrun,
+        _ => embedded_hal::spi::ErrorKind::Other,
+    };
+    let hal = Err::<(), _>(spi_kind).chain_kind(BusError::Transfer);
+    let i2c = Err::<(), _>(embedded_hal::i2c::ErrorKind::Bus).kind_err();
+    let _ = write!(Sink, "{:?} {:?}", hal, i2c);
+
+    // Futures.
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let future = pin!(Immediate(Err(spi)).chain_err(DriverError::InitFailed));
+    let polled = future.poll(&mut cx);
+    let matched = match option {
+        Ok(_) => 0,
+        Err(err) => match_error!(err, {
+            SpiError::Timeout => 1,
+            DriverError::_ => 2,
+            _ => 3,
+        }),
+    };
+
+    // Raw error data.
+    let mut data = ErrorData::new(input(5));
+    for i in 0..input(6) {
+        ErrorData::chain(&mut data, input(7), i);
+    }
+    data.truncate_chain(input(8) as usize);
+    let _ = data.pop_front();
+
+    // The result only keeps the values alive, so overflow checks are avoided.
+    let _ = write!(Sink, "{:?} {:?}", concat, concat.root_cause());
+    let mut result = concat.chain_len() as isize;
+    result ^= matched;
+    result ^= tree_matches;
+    result ^= set_first as isize;
+    result ^= (flags.bits() ^ faults.bits()) as isize;
+    result ^= nb_err.is_err() as isize;
+    result ^= blocked.map_or_else(|err| err.chain_len(), |_| 0) as isize;
+    result ^= polled.is_ready() as isize;
+    result ^= concat.root_cause().code() as isize;
+    result ^= concat.caused_by(Unlinked(input(9))) as isize;
+    result ^= concat.source().map_or(0, |source| source.chain_len()) as isize;
+    result ^= Err::<(), _>(concat)
+        .map_code(|_| AppError::Aborted)
+        .is_err() as isize;
+    for entry in DynError::from(Error::<SpiError>::from_raw(data)).entries() {
+        result ^= entry.code() as isize;
+    }
+    result
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum DriverError {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(DriverError))]
+#[repr(u8)]
+enum AppError {
+    /// aborted
+    Aborted,
+}
+
+/// Build `tests/no-panic`, a `no_std` binary whose panic handler references an undefined
+/// symbol, so that it only links if no panic path is left with the feature `no-panic`.
+#[test]
+#[cfg(all(feature = "no-panic", target_os = "linux", target_arch = "x86_64"))]
+fn link_without_panic_handler() {
+    let output = std::process::Command::new(env!("CARGO"))
+        .args(["build", "--release", "--manifest-path"])
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/no-panic/Cargo.toml"
+        ))
+        .env(
+            "CARGO_TARGET_DIR",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/target/no-panic"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+#[cfg(feature = "no-panic")]
+fn overflow() {
+    let mut data = ErrorData::new(1);
+    for code in 2..8 {
+        ErrorData::chain(&mut data, code, 0);
+    }
+    assert_eq!(data.chain_len(), ERROR_CHAIN_LEN);
+    assert_eq!(data.code(), 7);
+}
+
+#[test]
+#[cfg(feature = "no-panic")]
+fn unlinked() {
+    let err = DynError::from(DriverError::ReadoutFailed).chain(AppError::Aborted);
+    assert!(err.caused_by(DriverError::ReadoutFailed));
+
+    let err = DynError::from(AppError::Aborted).chain(SpiError::BusError);
+    assert!(err.caused_by(Unlinked(0)));
+
+    let mut err = Error::new(DriverError::InitFailed);
+    assert!(!err.concat(AppError::Aborted));
+    assert_eq!(
+        format!("{:?}", err),
+        "DriverError(0): init failed\n- <unlinked>(0): unknown error"
+    );
+}
+
+#[test]
+#[cfg(feature = "no-panic")]
+fn invalid_error_code() {
+    assert!(matches!(DriverError::from(9), DriverError::ReadoutFailed));
+}
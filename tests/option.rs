@@ -0,0 +1,78 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum ParseError {
+    /// missing field
+    MissingField,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(ParseError))]
+#[repr(u8)]
+enum ConfigError {
+    /// not found
+    NotFound,
+    /// invalid value
+    InvalidValue,
+}
+
+fn lookup(key: &str) -> Option<u32> {
+    match key {
+        "baud" => Some(9600),
+        _ => None,
+    }
+}
+
+#[test]
+fn ok_or_err() {
+    assert_eq!(lookup("baud").ok_or_err(ConfigError::NotFound), Ok(9600));
+    assert_eq!(
+        lookup("parity")
+            .ok_or_err(ConfigError::NotFound)
+            .unwrap_err()
+            .code(),
+        ConfigError::NotFound
+    );
+
+    let mut called = false;
+    assert_eq!(
+        lookup("baud").ok_or_else_err(|| {
+            called = true;
+            ConfigError::NotFound
+        }),
+        Ok(9600)
+    );
+    assert!(!called);
+    assert_eq!(
+        lookup("parity")
+            .ok_or_else_err(|| ConfigError::InvalidValue)
+            .unwrap_err()
+            .code(),
+        ConfigError::InvalidValue
+    );
+}
+
+#[test]
+fn ok_or_chain() {
+    let err = lookup("parity")
+        .ok_or_chain(ParseError::MissingField, ConfigError::NotFound)
+        .unwrap_err();
+    assert_eq!(
+        format!("{:?}", err),
+        "ConfigError(0): not found\n- ParseError(0): missing field"
+    );
+
+    let cause = Error::new(ParseError::MissingField);
+    let err = lookup("parity")
+        .ok_or_else_chain(|| cause, ConfigError::InvalidValue)
+        .unwrap_err();
+    assert_eq!(err.code(), ConfigError::InvalidValue);
+    assert!(err.caused_by(ParseError::MissingField));
+
+    assert_eq!(
+        lookup("baud").ok_or_chain(ParseError::MissingField, ConfigError::NotFound),
+        Ok(9600)
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error at {value:#04x}
+    BusError,
+    /// timeout
+    Timeout,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// Value must be in range [0, 256)
+    #[error("{variant}: {summary} (value={value})")]
+    InvalidValue,
+    #[error("{variant} after {value} retries")]
+    ReadoutFailed,
+}
+
+#[test]
+fn payload() {
+    let err = Error::with_payload(SpiError::BusError, 0x75u8);
+    assert_eq!(err.payload(), 0x75);
+    assert_eq!(err.without_payload(), Error::new(SpiError::BusError));
+    assert_eq!(format!("{:?}", err), "SpiError(0): bus error at 0x75");
+    assert_eq!(
+        format!("{:?}", Error::new(SpiError::BusError)),
+        "SpiError(0): bus error at ?"
+    );
+    assert_eq!(
+        format!("{:?}", Error::with_payload(SpiError::Timeout, 1u8)),
+        "SpiError(1): timeout"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            Error::with_payload(GyroAccError::ReadoutFailed, 3u8)
+        ),
+        "GyroAccError(1): ReadoutFailed after 3 retries"
+    );
+
+    let err = Error::with_payload(GyroAccError::InvalidValue, -3i16);
+    assert_eq!(
+        format!("{:?}", err),
+        "GyroAccError(0): InvalidValue: Value must be in range [0, 256) (value=-3)"
+    );
+}
+
+#[test]
+fn chain() {
+    let err = Error::with_payload(SpiError::BusError, 0x75u8).chain(GyroAccError::ReadoutFailed);
+    assert!(err.caused_by(SpiError::BusError));
+    assert_eq!(
+        format!("{:?}", err),
+        "GyroAccError(1): ReadoutFailed after ? retries\n- SpiError(0): bus error at ?"
+    );
+
+    let err: Result<(), Error<SpiError, u32>> = Err(Error::with_payload(SpiError::Timeout, 7));
+    let err = err.chain_err(GyroAccError::ReadoutFailed).unwrap_err();
+    assert_eq!(err.chain_len(), 1);
+}
+
+#[test]
+fn size() {
+    use core::mem::{align_of, size_of};
+
+    assert_eq!(size_of::<Error<SpiError>>(), 4);
+    assert_eq!(align_of::<Error<SpiError>>(), align_of::<u32>());
+    assert_eq!(size_of::<Error<SpiError, u16>>(), 8);
+}
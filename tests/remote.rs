@@ -0,0 +1,121 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+/// A module standing in for a HAL crate.
+mod hal {
+    pub mod spi {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum Error {
+            Overrun,
+            ModeFault,
+            Crc,
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum I2cError {
+        Nack(u8),
+        ArbitrationLost,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum Status {
+        Busy,
+        Timeout,
+    }
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(remote = "hal::spi::Error")]
+#[repr(u8)]
+enum SpiError {
+    /// overrun
+    Overrun,
+    /// mode fault
+    ModeFault,
+    /// CRC error
+    Crc,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(name = "I2c", remote = "hal::I2cError")]
+enum I2cError {
+    #[error("no ack from {0:#04x}")]
+    Nack(u8),
+    /// arbitration lost
+    ArbitrationLost,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(remote = "hal::Status")]
+#[repr(u8)]
+enum StatusError {
+    /// busy
+    Busy,
+    /// timeout
+    Timeout,
+    /// unknown
+    #[error(unknown)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError, I2cError))]
+#[repr(u8)]
+enum ImuError {
+    /// init failed
+    InitFailed,
+}
+
+fn spi_write() -> Result<(), hal::spi::Error> {
+    Err(hal::spi::Error::ModeFault)
+}
+
+fn imu_init() -> Result<(), Error<ImuError>> {
+    spi_write()
+        .map_err(SpiError::from)
+        .chain_err(ImuError::InitFailed)?;
+    Ok(())
+}
+
+#[test]
+fn conversions() {
+    assert!(matches!(
+        SpiError::from(hal::spi::Error::Crc),
+        SpiError::Crc
+    ));
+    assert_eq!(
+        hal::spi::Error::from(SpiError::Overrun),
+        hal::spi::Error::Overrun
+    );
+    assert!(matches!(
+        I2cError::from(hal::I2cError::Nack(0x68)),
+        I2cError::Nack(0x68)
+    ));
+    assert_eq!(
+        hal::I2cError::from(I2cError::ArbitrationLost),
+        hal::I2cError::ArbitrationLost
+    );
+    assert!(matches!(
+        StatusError::from(hal::Status::Busy),
+        StatusError::Busy
+    ));
+    assert!(matches!(
+        StatusError::from(hal::Status::Timeout),
+        StatusError::Timeout
+    ));
+}
+
+#[test]
+fn chain() {
+    let err = imu_init().unwrap_err();
+    assert!(err.caused_by(SpiError::ModeFault));
+    assert_eq!(
+        format!("{:?}", err),
+        "ImuError(0): init failed\n- SpiError(1): mode fault"
+    );
+
+    let err = Error::from_variant(I2cError::from(hal::I2cError::Nack(0x68)));
+    assert_eq!(format!("{:?}", err), "I2c(0): no ack from 0x68");
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum UartError {
+    /// unsupported clock
+    UnsupportedClock,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(UartError))]
+#[repr(u8)]
+enum ConfigError {
+    /// invalid baud rate
+    BaudRate,
+    /// invalid address
+    Address,
+    /// invalid mode
+    Mode,
+}
+
+fn check_baud_rate() -> Result<(), Error<ConfigError>> {
+    Err(UartError::UnsupportedClock.chain(ConfigError::BaudRate))
+}
+
+fn check_address() -> Result<u8, ConfigError> {
+    Err(ConfigError::Address)
+}
+
+fn check_mode() -> Result<(), ConfigError> {
+    Ok(())
+}
+
+#[test]
+fn check() {
+    let errors = ErrorSet::<4>::new()
+        .check(check_baud_rate)
+        .check(check_address)
+        .check(check_mode)
+        .into_result()
+        .unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors.dropped(), 0);
+
+    let mut iter = errors.iter();
+    assert!(iter.next().unwrap().caused_by(UartError::UnsupportedClock));
+    assert_eq!(iter.next().unwrap(), &DynError::new(ConfigError::Address));
+    assert_eq!(iter.next(), None);
+
+    assert!(ErrorSet::<4>::new().check(check_mode).into_result().is_ok());
+}
+
+#[test]
+fn overflow() {
+    let mut errors = ErrorSet::<2>::new();
+    errors.extend_from_results(vec![
+        Err(ConfigError::BaudRate),
+        Ok(()),
+        Err(ConfigError::Address),
+    ]);
+    assert!(errors.is_full());
+
+    let dropped = errors.push(ConfigError::Mode).unwrap_err();
+    assert_eq!(dropped, DynError::new(ConfigError::Mode));
+    assert_eq!(errors.dropped(), 1);
+    assert_eq!(errors.iter().count(), 2);
+
+    errors.clear();
+    assert!(errors.into_result().is_ok());
+}
+
+#[test]
+fn debug() {
+    let mut errors = ErrorSet::<2>::new()
+        .check(check_baud_rate)
+        .check(check_address);
+    assert_eq!(
+        format!("{:?}", errors),
+        "ConfigError(0): invalid baud rate\n- UartError(0): unsupported clock\n\nConfigError(1): invalid address"
+    );
+
+    let _ = errors.push(ConfigError::Mode);
+    assert!(format!("{:?}", errors).ends_with("invalid address\n\n(1 more error dropped)"));
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum GyroAccError {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(GyroAccError))]
+#[repr(u8)]
+enum ImuError {
+    /// calibration failed
+    CalibrationFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(ImuError))]
+#[repr(u8)]
+enum AppError {
+    /// startup failed
+    StartupFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, ErrorCategory)]
+#[error_category(links(SpiError), wide)]
+#[repr(u8)]
+enum WideError {
+    /// wide error
+    Wide = 200,
+}
+
+fn full_error() -> Error<AppError> {
+    SpiError::BusError
+        .chain(GyroAccError::ReadoutFailed)
+        .chain(ImuError::CalibrationFailed)
+        .chain(AppError::StartupFailed)
+}
+
+#[test]
+fn source() {
+    let err = full_error();
+    let source = err.source().unwrap();
+    assert_eq!(
+        format!("{:?}", source),
+        "ImuError(0): calibration failed\n- GyroAccError(1): readout failed\n- SpiError(0): bus error"
+    );
+    let source = source.source().unwrap();
+    assert_eq!(source.code(), 1);
+    assert_eq!(source.chain_len(), 1);
+    let source = source.source().unwrap();
+    assert!(source.is::<SpiError>());
+    assert!(source.source().is_none());
+
+    assert!(Error::new(SpiError::BusError).source().is_none());
+
+    let err = SpiError::BusError.chain(WideError::Wide);
+    assert_eq!(
+        format!("{:?}", err.source().unwrap()),
+        "SpiError(0): bus error"
+    );
+}
+
+#[test]
+fn split_first() {
+    let (code, source) = full_error().split_first();
+    assert!(code == AppError::StartupFailed);
+
+    // Re-chain the source with a different error code.
+    let source: Error<ImuError> = source.unwrap().try_into().unwrap();
+    let err = source.chain(AppError::StartupFailed);
+    assert_eq!(err, full_error());
+
+    let (code, source) = Error::new(GyroAccError::InitFailed).split_first();
+    assert!(code == GyroAccError::InitFailed);
+    assert!(source.is_none());
+}
+
+#[test]
+fn truncate() {
+    let mut err = full_error();
+    err.truncate(5);
+    assert_eq!(err, full_error());
+
+    err.truncate(2);
+    assert_eq!(
+        format!("{:?}", err),
+        "AppError(0): startup failed\n- ImuError(0): calibration failed"
+    );
+    err.truncate(0);
+    assert_eq!(err.chain_len(), 0);
+    assert_eq!(err.code(), AppError::StartupFailed);
+
+    let mut err: DynError = full_error().into();
+    err.truncate(3);
+    assert_eq!(err.chain_len(), 2);
+    assert!(!err.caused_by(SpiError::BusError));
+
+    let mut err = SpiError::BusError.chain(WideError::Wide);
+    err.truncate(1);
+    assert_eq!(err.chain_len(), 0);
+    assert!(err.code() == WideError::Wide);
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum AccError {
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum GyroError {
+    /// calibration failed
+    Calibration,
+    /// timeout
+    Timeout,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum FusionError {
+    /// sensor fusion failed
+    Failed,
+}
+
+fn fusion_error<const N: usize>() -> ErrorTree<N> {
+    join(
+        SpiError::BusError.chain(AccError::ReadoutFailed),
+        GyroError::Timeout,
+    )
+    .chain(FusionError::Failed)
+}
+
+#[test]
+fn join_chain() {
+    let err: ErrorTree<4> = fusion_error();
+
+    assert_eq!(err.len(), 3);
+    assert!(matches!(
+        err.root().unwrap().code_of_category(),
+        Some(FusionError::Failed)
+    ));
+    assert_eq!(err.children(0).count(), 2);
+    assert_eq!(err.children(1).count(), 0);
+
+    assert!(err.caused_by(FusionError::Failed));
+    assert!(err.caused_by(SpiError::BusError));
+    assert!(err.caused_by(GyroError::Timeout));
+    assert!(!err.caused_by(GyroError::Calibration));
+    assert!(matches!(
+        err.code_of_category(),
+        Some(AccError::ReadoutFailed)
+    ));
+
+    let forest: ErrorTree<4> = join(AccError::ReadoutFailed, GyroError::Timeout);
+    assert!(forest.root().is_none());
+}
+
+#[test]
+fn nested() {
+    let err: ErrorTree<8> = join(fusion_error(), GyroError::Calibration).chain(FusionError::Failed);
+
+    assert_eq!(err.len(), 5);
+    assert_eq!(err.children(0).count(), 2);
+    assert_eq!(
+        format!("{:?}", err),
+        "FusionError(0): sensor fusion failed
+  + FusionError(0): sensor fusion failed
+    + AccError(0): readout failed
+      - SpiError(0): bus error
+    + GyroError(1): timeout
+  + GyroError(0): calibration failed"
+    );
+}
+
+#[cfg(not(feature = "panic-on-overflow"))]
+#[test]
+fn overflow() {
+    let err: ErrorTree<3> = fusion_error().join(GyroError::Calibration);
+    assert_eq!(err.len(), 3);
+    assert!(!err.caused_by(GyroError::Calibration));
+
+    let err: ErrorTree<3> = err.chain(FusionError::Failed);
+    assert_eq!(err.len(), 3);
+    assert!(!err.caused_by(GyroError::Timeout));
+}
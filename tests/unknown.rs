@@ -0,0 +1,81 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[non_exhaustive]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout = 5,
+    /// unknown
+    #[error(unknown)]
+    Unknown = 15,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum DriverError {
+    /// init failed
+    InitFailed,
+    #[error(unknown)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+enum SensorError {
+    #[error("timeout after {0} ms")]
+    Timeout(u16),
+    /// unknown sensor error
+    #[error(unknown)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[non_exhaustive]
+#[repr(u8)]
+enum NonExhaustiveError {
+    /// first
+    First,
+    /// second
+    Second,
+}
+
+#[test]
+fn unknown_code() {
+    assert!(matches!(SpiError::from(0), SpiError::BusError));
+    assert!(matches!(SpiError::from(5), SpiError::Timeout));
+    assert!(matches!(SpiError::from(11), SpiError::Unknown));
+    assert!(matches!(SpiError::from(15), SpiError::Unknown));
+    assert!(matches!(DriverError::from(7), DriverError::Unknown));
+    assert!(matches!(SensorError::from(5), SensorError::Unknown));
+    assert!(matches!(
+        SensorError::from_payload(5, 100),
+        SensorError::Unknown
+    ));
+
+    assert!(matches!(
+        NonExhaustiveError::from(1),
+        NonExhaustiveError::Second
+    ));
+}
+
+#[test]
+fn raw_code() {
+    let err: Error<SpiError> = Error::new_raw(11);
+    assert!(matches!(err.code(), SpiError::Unknown));
+    assert_eq!(err.raw_code(), 11);
+    assert_eq!(format!("{:?}", err), "SpiError(11): unknown");
+
+    let err = err.chain(DriverError::InitFailed);
+    assert_eq!(
+        format!("{:?}", err),
+        "DriverError(0): init failed\n- SpiError(11): unknown"
+    );
+    assert_eq!(
+        format!("{:?}", DynError::from(Error::<DriverError>::new_raw(9))),
+        "DriverError(9): Unknown"
+    );
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum FooError {
+    /// first
+    First,
+    /// second
+    Second,
+    /// third
+    Third,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum BarError {
+    /// failed
+    Failed,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(BarError))]
+#[repr(u8)]
+enum AppError {
+    /// aborted
+    Aborted,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide)]
+#[repr(u8)]
+enum StatusError {
+    /// busy
+    Busy = 0x25,
+}
+
+#[test]
+fn linked() {
+    let err = DynError::from(SpiError::BusError).chain_or_unlinked(BarError::Failed);
+    assert!(err.caused_by(SpiError::BusError));
+    assert_eq!(
+        format!("{:?}", err),
+        "BarError(0): failed\n- SpiError(0): bus error"
+    );
+}
+
+#[test]
+fn unlinked() {
+    let err = DynError::from(SpiError::BusError.chain(FooError::Third))
+        .chain_or_unlinked(BarError::Failed)
+        .chain(AppError::Aborted);
+    assert_eq!(err.chain_len(), 3);
+    assert!(err.caused_by(Unlinked(2)));
+    assert!(!err.caused_by(FooError::Third));
+    assert_eq!(
+        format!("{:?}", err),
+        "AppError(0): aborted\n- BarError(0): failed\n- <unlinked>(2): unknown error\n- <unlinked>(0): unknown error"
+    );
+
+    let entries: Vec<_> = err.entries().collect();
+    assert!(entries[1].is::<BarError>());
+    assert_eq!(entries[2].downcast::<Unlinked>(), Some(Unlinked(2)));
+    assert!(err.root_cause().is::<Unlinked>());
+
+    let source = err.source().unwrap().source().unwrap();
+    assert!(source.is::<Unlinked>());
+    assert_eq!(source.chain_len(), 1);
+}
+
+#[test]
+fn error_tree_keeps_categories() {
+    let err = DynError::from(SpiError::BusError.chain(FooError::Third));
+    let tree = ErrorTree::<2>::from(err).chain(BarError::Failed);
+    assert!(tree.caused_by(FooError::Third));
+    assert!(tree.caused_by(SpiError::BusError));
+    assert_eq!(
+        format!("{:?}", tree),
+        "BarError(0): failed\n  + FooError(2): third\n    - SpiError(0): bus error"
+    );
+}
+
+#[test]
+fn wide() {
+    let err = DynError::from(StatusError::Busy).chain_or_unlinked(BarError::Failed);
+    assert_eq!(err.chain_len(), 1);
+    assert!(err.caused_by(Unlinked(0x5)));
+
+    let mut data = ErrorData::new(1);
+    data.push_front(2, 6);
+    data.push_front(3, 0);
+    assert_eq!(data.push_front(StatusError::Busy.into(), 6), None);
+    assert!(data.is_wide());
+    assert_eq!(data.code(), 0x25);
+    assert_eq!(data.chain_len(), 3);
+}
+
+fn full_chain_err() -> Error<FooError> {
+    let err = SpiError::BusError
+        .chain(BarError::Failed)
+        .chain(AppError::Aborted);
+    let err = DynError::from(err).chain_or_unlinked(FooError::First);
+    assert_eq!(err.chain_len(), 3);
+    DynError::from(err).chain_or_unlinked(FooError::Second)
+}
+
+#[test]
+fn full_chain() {
+    // An unlinked error code fits into the last slot of the chain.
+    let err = full_chain_err();
+    assert_eq!(err.chain_len(), ERROR_CHAIN_LEN);
+    assert!(err.caused_by(Unlinked(0)));
+    assert_eq!(
+        format!("{:?}", err),
+        "FooError(1): second\n- <unlinked>(0): unknown error\n- <unlinked>(0): unknown error\n- <unlinked>(0): unknown error\n- <unlinked>(0): unknown error"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+#[should_panic(expected = "chaining two errors overflowed; error chain is full")]
+fn overflow() {
+    let _err = DynError::from(full_chain_err()).chain_or_unlinked(FooError::Third);
+}
+
+#[test]
+#[cfg(not(all(feature = "panic-on-overflow", not(feature = "no-panic"))))]
+fn overflow() {
+    let err = DynError::from(full_chain_err()).chain_or_unlinked(FooError::Third);
+    assert_eq!(err.chain_len(), ERROR_CHAIN_LEN);
+    assert!(matches!(err.code(), FooError::Third));
+}
+
+#[test]
+fn unlinked_in_last_slot() {
+    let mut data = ErrorData::new(1);
+    data.push_front(2, 6);
+    data.push_front(3, 0);
+    data.push_front(4, 0);
+    assert_eq!(data.chain_len(), 3);
+
+    assert_eq!(data.push_front(5, 0), None);
+    assert_eq!(data.chain_len(), ERROR_CHAIN_LEN);
+    assert!(!data.is_wide());
+    assert_eq!(data.code(), 5);
+
+    // The unlinked error code is the one that overflows next.
+    assert_eq!(data.push_front(6, 0), Some((1, 6)));
+    assert_eq!(data.chain_len(), ERROR_CHAIN_LEN);
+}
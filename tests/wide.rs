@@ -0,0 +1,125 @@
+#![cfg(feature = "derive")]
+use embedded_error_chain::*;
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[repr(u8)]
+enum SpiError {
+    /// bus error
+    BusError,
+    /// timeout
+    Timeout = 5,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(links(SpiError))]
+#[repr(u8)]
+enum DriverError {
+    /// init failed
+    InitFailed,
+    /// readout failed
+    ReadoutFailed,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(u8)]
+enum HalStatus {
+    Ok = 0,
+    Busy = 17,
+    Timeout = 42,
+    Unknown = 255,
+}
+
+#[derive(Clone, Copy, ErrorCategory)]
+#[error_category(wide, links(DriverError, SpiError))]
+#[repr(u8)]
+enum HalError {
+    /// ok
+    Ok = HalStatus::Ok as u8,
+    /// busy
+    Busy = HalStatus::Busy as u8,
+    /// timeout
+    Timeout = HalStatus::Timeout as u8,
+    /// unknown
+    Unknown = HalStatus::Unknown as u8,
+}
+
+#[test]
+fn wide_code() {
+    let err = Error::new(HalError::Timeout);
+    assert_eq!(err.chain_len(), 0);
+    assert!(matches!(err.code(), HalError::Timeout));
+    assert_eq!(ErrorData::from(err).code(), 42);
+    assert_eq!(format!("{:?}", err), "HalError(42): timeout");
+
+    assert_eq!(
+        Error::new(SpiError::BusError).chain_capacity(),
+        ERROR_CHAIN_LEN
+    );
+
+    let err = DynError::from(HalError::Unknown);
+    assert_eq!(err.code(), 255);
+    assert!(matches!(err.code_of_category(), Some(HalError::Unknown)));
+}
+
+#[test]
+fn chain() {
+    let err = SpiError::Timeout
+        .chain(DriverError::ReadoutFailed)
+        .chain(HalError::Busy);
+
+    let data = ErrorData::from(err);
+    assert!(data.is_wide());
+    assert_eq!(data.chain_capacity(), ERROR_CHAIN_LEN - 1);
+    assert_eq!(err.chain_capacity(), ERROR_CHAIN_LEN - 1);
+    assert_eq!(DynError::from(err).chain_capacity(), ERROR_CHAIN_LEN - 1);
+    assert_eq!(err.chain_len(), 2);
+    assert!(matches!(err.code(), HalError::Busy));
+    assert!(err.caused_by(SpiError::Timeout));
+    assert!(err.caused_by(DriverError::ReadoutFailed));
+    assert_eq!(
+        format!("{:?}", err),
+        "HalError(17): busy\n- DriverError(1): readout failed\n- SpiError(5): timeout"
+    );
+
+    let mut data = ErrorData::from(err);
+    assert_eq!(data.set_code(HalError::Ok.into()), 17);
+    assert!(!data.is_wide());
+    assert_eq!(data.chain_len(), 2);
+    assert_eq!(data.set_code(HalError::Unknown.into()), 0);
+    assert_eq!(data.code(), 255);
+    assert_eq!(data.chain_len(), 2);
+}
+
+/// Error data with a narrow error code and a full chain.
+fn full_chain_data() -> ErrorData {
+    let mut data = ErrorData::new(HalError::Ok.into());
+    for code in 0..ERROR_CHAIN_LEN as ErrorCode {
+        ErrorData::chain(&mut data, code, 0);
+    }
+    data
+}
+
+#[cfg(all(feature = "panic-on-overflow", not(feature = "no-panic")))]
+#[test]
+#[should_panic(expected = "setting a wide error code overflowed; error chain is full")]
+fn set_code_overflow() {
+    full_chain_data().set_code(HalError::Busy.into());
+}
+
+#[cfg(not(all(feature = "panic-on-overflow", not(feature = "no-panic"))))]
+#[test]
+fn set_code_overflow() {
+    let mut data = full_chain_data();
+    data.set_code(HalError::Busy.into());
+    assert_eq!(data.code(), 17);
+    assert_eq!(data.chain_len(), ERROR_CHAIN_LEN - 1);
+}
+
+#[test]
+fn flags() {
+    let mut flags = ErrorFlags::new();
+    assert!(flags.set(HalError::Ok));
+    assert!(!flags.set(HalError::Timeout));
+    assert!(!flags.contains(HalError::Timeout));
+    assert_eq!(flags.len(), 1);
+}